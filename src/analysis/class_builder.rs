@@ -143,5 +143,6 @@ fn analyze_property(
         bounds,
         version: prop_version,
         deprecated_version: prop.deprecated_version,
+        default_value: prop.default_value.clone(),
     })
 }
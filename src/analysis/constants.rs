@@ -50,7 +50,7 @@ pub fn analyze<F: Borrow<library::Constant>>(
             .iter()
             .find_map(|c| c.cfg_condition.clone());
 
-        let name = nameutil::mangle_keywords(&*constant.name).into_owned();
+        let name = nameutil::mangle_keywords(&*constant.name, env.config.edition).into_owned();
 
         consts.push(Info {
             name,
@@ -97,6 +97,7 @@ impl ConversionType {
             List(_) => Self::Pointer,
             SList(_) => Self::Pointer,
             PtrArray(_) => Self::Pointer,
+            HashTable(..) => Self::Pointer,
             Function(super::library::Function { name, .. }) if name == "AsyncReadyCallback" => {
                 Self::Direct
             }
@@ -0,0 +1,143 @@
+//! Trace of the decisions taken for a single function, for the `--explain`
+//! command line option.
+
+use std::fmt::Write;
+
+use super::{functions, not_bound::analyzed_functions};
+use crate::{
+    config::{gobjects::GStatus, matchable::Matchable},
+    env::Env,
+    library::{self, FunctionKind, TypeId, MAIN_NAMESPACE},
+};
+
+fn status_name(status: GStatus) -> &'static str {
+    match status {
+        GStatus::Manual => "manual",
+        GStatus::Generate => "generate",
+        GStatus::Ignore => "ignore",
+    }
+}
+
+/// Describes how the function of the main namespace with the given C
+/// identifier is configured and why it is (or isn't) generated.
+pub fn explain(env: &Env, c_identifier: &str) -> Result<String, String> {
+    let ns = env.library.namespace(MAIN_NAMESPACE);
+    let is_func = |f: &&library::Function| f.c_identifier.as_deref() == Some(c_identifier);
+
+    let mut owner = None;
+    for (id, typ) in ns.types.iter().enumerate() {
+        let Some(typ) = typ else { continue };
+        if let Some(func) = typ.functions().iter().find(is_func) {
+            let tid = TypeId {
+                ns_id: MAIN_NAMESPACE,
+                id: id as u32,
+            };
+            owner = Some((
+                format!("{}.{}", ns.name, typ.get_name()),
+                Some((tid, typ)),
+                func,
+            ));
+            break;
+        }
+    }
+    let global_functions = ns
+        .functions
+        .iter()
+        .filter(|f| f.kind == FunctionKind::Global)
+        .collect::<Vec<_>>();
+    let owner = owner.or_else(|| {
+        global_functions
+            .iter()
+            .copied()
+            .find(is_func)
+            .map(|func| (format!("{}.*", ns.name), None, func))
+    });
+    let Some((full_name, typ, func)) = owner else {
+        return Err(format!(
+            "No function `{c_identifier}` in the {} namespace",
+            ns.name
+        ));
+    };
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{c_identifier}: function `{}` of `{full_name}`",
+        func.name
+    )
+    .unwrap();
+
+    let Some(obj) = env.config.objects.get(&full_name) else {
+        writeln!(out, "`{full_name}` isn't in the config: not bound").unwrap();
+        return Ok(out);
+    };
+    writeln!(
+        out,
+        "`{full_name}` is configured as {}",
+        status_name(obj.status)
+    )
+    .unwrap();
+    if !obj.status.need_generate() {
+        writeln!(out, "the function isn't analyzed").unwrap();
+        return Ok(out);
+    }
+
+    let configured_functions = obj.functions.matched(&func.name);
+    if configured_functions.is_empty() {
+        writeln!(out, "no [[object.function]] rule matched").unwrap();
+    }
+    for f in &configured_functions {
+        write!(out, "matched rule `{}`: {}", f.ident, status_name(f.status)).unwrap();
+        if let Some(introspectable) = f.introspectable {
+            write!(out, ", introspectable = {introspectable}").unwrap();
+        }
+        if let Some(rename) = &f.rename {
+            write!(out, ", rename = {rename:?}").unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    let (type_tid, analyzed, functions) = match typ {
+        Some((tid, typ)) => (
+            Some(tid),
+            analyzed_functions(env, &full_name, typ),
+            typ.functions().iter().collect::<Vec<_>>(),
+        ),
+        None => (
+            None,
+            env.analysis
+                .global_functions
+                .as_ref()
+                .map(|info| &info.functions[..]),
+            global_functions,
+        ),
+    };
+    if let Some(sibling) = analyzed.and_then(|analyzed| functions::variadic_sibling(analyzed, func))
+    {
+        writeln!(out, "bound through `{}`", sibling.glib_name).unwrap();
+        return Ok(out);
+    }
+    if let Some(reason) =
+        functions::dropped_reason(env, func, &functions, type_tid, obj, &configured_functions)
+    {
+        writeln!(out, "skipped: {reason}").unwrap();
+        return Ok(out);
+    }
+
+    let info = analyzed
+        .into_iter()
+        .flatten()
+        .find(|info| info.glib_name == c_identifier);
+    match info {
+        None => writeln!(out, "skipped by the analysis of `{full_name}`").unwrap(),
+        Some(info) if info.status.manual() => writeln!(out, "bound manually").unwrap(),
+        Some(info) if info.commented => {
+            writeln!(out, "generated commented out:").unwrap();
+            for reason in &info.skip_reasons {
+                writeln!(out, "  - {reason}").unwrap();
+            }
+        }
+        Some(info) => writeln!(out, "generated as `{}`", info.codegen_name()).unwrap(),
+    }
+    Ok(out)
+}
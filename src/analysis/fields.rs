@@ -0,0 +1,140 @@
+use log::warn;
+
+use super::{
+    c_type::rustify_pointers, conversion_type::ConversionType, functions, imports::Imports,
+    rust_type::RustType,
+};
+use crate::{
+    config::{gobjects::GObject, matchable::Matchable},
+    env::Env,
+    library::{self, Nullable, ParameterDirection},
+    nameutil::use_glib_type,
+    traits::*,
+    version::Version,
+};
+
+/// Accessors of a public record field.
+#[derive(Debug)]
+pub struct Info {
+    /// Name of the field in the `-sys` structure, also used for the getter.
+    pub name: String,
+    pub typ: library::TypeId,
+    /// Type returned by the getter, also taken by the setter.
+    pub rust_type: String,
+    pub conversion_type: ConversionType,
+    pub setter_name: Option<String>,
+    pub version: Option<Version>,
+    pub cfg_condition: Option<String>,
+}
+
+pub fn analyze(
+    env: &Env,
+    fields: &[library::Field],
+    obj: &GObject,
+    boxed_inline: bool,
+    functions: &[functions::Info],
+    imports: &mut Imports,
+) -> Vec<Info> {
+    let mut infos = Vec::new();
+
+    for field in fields {
+        let configured_fields = obj.fields.matched(&field.name);
+        // Accessors are opt-in
+        if configured_fields.is_empty()
+            || !configured_fields.iter().all(|f| f.status.need_generate())
+        {
+            continue;
+        }
+        if field.private || field.bits.is_some() {
+            warn!(
+                "Can't generate accessors for private or bit field {}.{}",
+                obj.name, field.name
+            );
+            continue;
+        }
+
+        let conversion_type = ConversionType::of(env, field.typ);
+        let supported = match conversion_type {
+            ConversionType::Direct | ConversionType::Scalar => !matches!(
+                env.library.type_(field.typ),
+                library::Type::Function(_) | library::Type::Basic(library::Basic::UniChar)
+            ),
+            // Embedded structures can't be taken ownership of
+            ConversionType::Pointer => {
+                field
+                    .c_type
+                    .as_deref()
+                    .is_some_and(|c_type| rustify_pointers(c_type).0.starts_with('*'))
+                    && !matches!(
+                        env.library.type_(field.typ),
+                        library::Type::Basic(library::Basic::Pointer)
+                            | library::Type::CArray(_)
+                            | library::Type::FixedArray(..)
+                            | library::Type::List(_)
+                            | library::Type::SList(_)
+                            | library::Type::PtrArray(_)
+                            | library::Type::HashTable(..)
+                    )
+            }
+            _ => false,
+        };
+        if !supported {
+            warn!(
+                "Can't generate accessors for field {}.{} of type {}",
+                obj.name,
+                field.name,
+                field.typ.full_name(&env.library)
+            );
+            continue;
+        }
+
+        let is_pointer = conversion_type == ConversionType::Pointer;
+        let nullable = configured_fields
+            .iter()
+            .find_map(|f| f.nullable)
+            .unwrap_or(Nullable(is_pointer));
+        let Ok(rust_type) = RustType::builder(env, field.typ)
+            .direction(ParameterDirection::Return)
+            .nullable(nullable)
+            .try_build()
+        else {
+            continue;
+        };
+
+        if functions
+            .iter()
+            .any(|f| f.status.need_generate() && f.codegen_name() == field.name)
+        {
+            warn!(
+                "Field {}.{} clashes with a method of the same name",
+                obj.name, field.name
+            );
+            continue;
+        }
+
+        imports.add_used_types(rust_type.used_types());
+        if conversion_type != ConversionType::Direct {
+            imports.add(&use_glib_type(env, "translate::*"));
+        }
+
+        // Setting a pointer would require to know who owns the previous
+        // value, and only inline records can be mutated in place
+        let setter_name =
+            (boxed_inline && !is_pointer && configured_fields.iter().all(|f| f.setter))
+                .then(|| format!("set_{}", field.name.trim_end_matches('_')));
+
+        infos.push(Info {
+            name: field.name.clone(),
+            typ: field.typ,
+            rust_type: rust_type.into_string(),
+            conversion_type,
+            setter_name,
+            version: configured_fields.iter().filter_map(|f| f.version).min(),
+            cfg_condition: configured_fields
+                .iter()
+                .find_map(|f| f.cfg_condition.clone()),
+        });
+    }
+
+    infos
+}
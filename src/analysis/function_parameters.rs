@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
 use super::{
-    conversion_type::ConversionType, out_parameters::can_as_return,
-    override_string_type::override_string_type_parameter, ref_mode::RefMode, rust_type::RustType,
-    try_from_glib::TryFromGlib,
+    conversion_type::ConversionType, functions::is_array_with_scalar_elements,
+    out_parameters::can_as_return, override_string_type::override_string_type_parameter,
+    ref_mode::RefMode, rust_type::RustType, try_from_glib::TryFromGlib,
 };
 use crate::{
     analysis::{self, bounds::Bounds},
@@ -99,6 +99,12 @@ pub enum TransformationType {
         nullable: bool,
         move_: bool,
     },
+    /// Filled into a local `GHashTable` before the call
+    ToGlibHashTable {
+        name: String,
+        typ: library::TypeId,
+        transfer: library::Transfer,
+    },
     ToGlibBorrow,
     ToGlibUnknown {
         name: String,
@@ -119,6 +125,7 @@ impl TransformationType {
             Self::ToGlibDirect { .. }
                 | Self::ToGlibScalar { .. }
                 | Self::ToGlibPointer { .. }
+                | Self::ToGlibHashTable { .. }
                 | Self::ToGlibBorrow
                 | Self::ToGlibUnknown { .. }
                 | Self::ToSome(_)
@@ -217,7 +224,7 @@ pub fn analyze(
         let name = if par.instance_parameter {
             par.name.clone()
         } else {
-            nameutil::mangle_keywords(&*par.name).into_owned()
+            nameutil::mangle_keywords(&*par.name, env.config.edition).into_owned()
         };
         if par.instance_parameter {
             correction_instance = 1;
@@ -266,7 +273,12 @@ pub fn analyze(
                 // FIXME: drop the condition here once we have figured out how to handle
                 // the Vec<T> use case, e.g with something like PtrSlice
 
-                if matches!(env.library.type_(typ), library::Type::CArray(_)) {
+                if matches!(
+                    env.library.type_(typ),
+                    library::Type::CArray(_)
+                        | library::Type::FixedArray(..)
+                        | library::Type::HashTable(..)
+                ) {
                     false
                 } else {
                     transfer == Transfer::Full && par.direction.is_in()
@@ -284,7 +296,7 @@ pub fn analyze(
             array_par = detect_length(env, pos, par, function_parameters);
         }
         if let Some(array_par) = array_par {
-            let mut array_name = nameutil::mangle_keywords(&array_par.name);
+            let mut array_name = nameutil::mangle_keywords(&array_par.name, env.config.edition);
             if let Some(bound_type) = Bounds::type_for(env, array_par.typ) {
                 array_name = (array_name.into_owned()
                     + &Bounds::get_to_glib_extra(
@@ -389,12 +401,47 @@ pub fn analyze(
                     needs_into,
                 }
             }
+            // The C function takes a pointer to the whole array
+            ConversionType::Pointer
+                if matches!(env.library.type_(typ), library::Type::FixedArray(..)) =>
+            {
+                TransformationType::ToGlibPointer {
+                    name,
+                    instance_parameter: par.instance_parameter,
+                    transfer,
+                    ref_mode: RefMode::ByRefFake,
+                    to_glib_extra: if is_array_with_scalar_elements(env, typ) {
+                        ".map(|v| v.into_glib()).as_ptr()".to_owned()
+                    } else {
+                        ".as_ptr()".to_owned()
+                    },
+                    explicit_target_type: Default::default(),
+                    pointer_cast: " as *mut _".to_owned(),
+                    in_trait,
+                    nullable: *nullable,
+                    move_,
+                }
+            }
+            ConversionType::Pointer
+                if matches!(env.library.type_(typ), library::Type::HashTable(..)) =>
+            {
+                TransformationType::ToGlibHashTable {
+                    name,
+                    typ,
+                    transfer,
+                }
+            }
             ConversionType::Pointer => TransformationType::ToGlibPointer {
                 name,
                 instance_parameter: par.instance_parameter,
                 transfer,
                 ref_mode,
-                to_glib_extra: Default::default(),
+                // The elements are passed as their integer values
+                to_glib_extra: if is_array_with_scalar_elements(env, typ) {
+                    ".iter().map(|v| v.into_glib()).collect::<Vec<_>>()".to_owned()
+                } else {
+                    Default::default()
+                },
                 explicit_target_type: Default::default(),
                 pointer_cast: if matches!(env.library.type_(typ), library::Type::CArray(_))
                     && par.c_type == "gpointer"
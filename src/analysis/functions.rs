@@ -5,12 +5,13 @@
 
 use std::{
     borrow::Borrow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
 };
 
 use log::warn;
 
-use super::{namespaces::NsId, special_functions};
+use super::{namespaces::NsId, special_functions, supertypes};
 use crate::{
     analysis::{
         self,
@@ -59,6 +60,169 @@ pub struct AsyncFuture {
     pub assertion: SafetyAssertionMode,
 }
 
+/// `*_stream()` variant of a function taking a callback called repeatedly,
+/// until its destroy notify is called.
+#[derive(Clone, Debug)]
+pub struct CallbackStream {
+    pub name: String,
+    /// Name of the callback parameter, replaced by a closure sending its
+    /// arguments to the stream.
+    pub callback: String,
+    pub nullable: library::Nullable,
+    /// Names of the parameters of the closure.
+    pub parameters: Vec<String>,
+    /// Owned values sent to the stream, one for each parameter.
+    pub values: Vec<String>,
+    pub item_type: String,
+}
+
+/// Variadic function, which can't be called from Rust, bound through this
+/// function taking an array instead.
+#[derive(Clone, Debug)]
+pub struct Variadic {
+    /// C identifier of the variadic function.
+    pub glib_name: String,
+    /// Set for the NULL-terminated key/value pairs, passed as a slice to a
+    /// separate method instead of renaming this one.
+    pub key_values: Option<KeyValues>,
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyValues {
+    /// Name of the method taking the pairs.
+    pub name: String,
+    /// Name of its parameter.
+    pub pairs: String,
+    /// Parameters of this function taking the keys and the values.
+    pub keys: String,
+    pub values: String,
+}
+
+/// Why a function isn't analyzed, or is generated commented out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    IgnoredByConfig {
+        rule: String,
+    },
+    IntrospectableDisabledByConfig {
+        rule: String,
+    },
+    NotIntrospectable,
+    ShadowedBy(String),
+    /// Deprecated before the minimum supported version.
+    TotallyDeprecated(Version),
+    /// Provided with the same signature by a parent type since a version not
+    /// newer than the minimum supported one, `None` if it always was.
+    VersionTooLow(Option<Version>),
+    /// `None` for the return value.
+    UnsupportedType {
+        parameter: Option<String>,
+        type_name: String,
+    },
+    Variadic,
+    MissingLengthParameter(String),
+    /// `None` when the issue isn't about a single callback.
+    UnsupportedCallback {
+        parameter: Option<String>,
+        reason: String,
+    },
+    UnsupportedAsync(&'static str),
+    UnsupportedOutParameters,
+    ReturnsCallback,
+}
+
+impl SkipReason {
+    /// Whether the function is dropped instead of generated commented out.
+    pub fn is_dropped(&self) -> bool {
+        matches!(
+            self,
+            Self::IgnoredByConfig { .. }
+                | Self::IntrospectableDisabledByConfig { .. }
+                | Self::NotIntrospectable
+                | Self::ShadowedBy(_)
+                | Self::TotallyDeprecated(_)
+                | Self::VersionTooLow(_)
+        )
+    }
+
+    fn unsupported_type(env: &Env, parameter: Option<&str>, typ: library::TypeId) -> Self {
+        let type_name = match env.library.type_(typ) {
+            library::Type::Basic(library::Basic::VarArgs) => return Self::Variadic,
+            // The library names of containers are only their element ids
+            library::Type::CArray(_)
+            | library::Type::FixedArray(..)
+            | library::Type::Array(_)
+            | library::Type::PtrArray(_)
+            | library::Type::HashTable(..)
+            | library::Type::List(_)
+            | library::Type::SList(_) => match RustType::try_new(env, typ) {
+                Err(TypeError::Unimplemented(name) | TypeError::Mismatch(name)) => name,
+                _ => typ.full_name(&env.library),
+            },
+            _ => typ.full_name(&env.library),
+        };
+        Self::UnsupportedType {
+            parameter: parameter.map(ToOwned::to_owned),
+            type_name,
+        }
+    }
+
+    fn unsupported_callback(parameter: Option<&str>, reason: impl Into<String>) -> Self {
+        Self::UnsupportedCallback {
+            parameter: parameter.map(ToOwned::to_owned),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IgnoredByConfig { rule } => write!(f, "ignored by the `{rule}` rule"),
+            Self::IntrospectableDisabledByConfig { rule } => {
+                write!(f, "`introspectable = false` set by the `{rule}` rule")
+            }
+            Self::NotIntrospectable => f.write_str("not introspectable"),
+            Self::ShadowedBy(name) => write!(f, "shadowed by `{name}`, bound under this name"),
+            Self::TotallyDeprecated(version) => write!(
+                f,
+                "deprecated in {version}, not after the minimum supported version"
+            ),
+            Self::VersionTooLow(Some(version)) => write!(
+                f,
+                "provided by a parent type since {version}, not after the minimum supported version"
+            ),
+            Self::VersionTooLow(None) => f.write_str("provided by a parent type"),
+            Self::UnsupportedType {
+                parameter: Some(parameter),
+                type_name,
+            } => write!(
+                f,
+                "parameter `{parameter}` has unsupported type `{type_name}`"
+            ),
+            Self::UnsupportedType {
+                parameter: None,
+                type_name,
+            } => write!(f, "return value has unsupported type `{type_name}`"),
+            Self::Variadic => f.write_str("variadic arguments aren't supported"),
+            Self::MissingLengthParameter(parameter) => {
+                write!(f, "no length parameter for the array `{parameter}`")
+            }
+            Self::UnsupportedCallback {
+                parameter: Some(parameter),
+                reason,
+            } => write!(f, "callback `{parameter}`: {reason}"),
+            Self::UnsupportedCallback {
+                parameter: None,
+                reason,
+            } => write!(f, "callbacks: {reason}"),
+            Self::UnsupportedAsync(reason) => write!(f, "async: {reason}"),
+            Self::UnsupportedOutParameters => f.write_str("unsupported out parameters"),
+            Self::ReturnsCallback => f.write_str("returns a callback"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Info {
     pub name: String,
@@ -89,11 +253,15 @@ pub struct Info {
     pub destroys: Vec<Trampoline>,
     pub remove_params: Vec<usize>,
     pub async_future: Option<AsyncFuture>,
+    pub callback_stream: Option<CallbackStream>,
+    pub variadic: Option<Variadic>,
     /// Whether the function is hidden (an implementation detail)
     /// Like the ref/unref/copy/free functions
     pub hidden: bool,
     /// Whether the function can't be generated
     pub commented: bool,
+    /// Why the function is commented out
+    pub skip_reasons: Vec<SkipReason>,
     /// In order to generate docs links we need to know in which namespace
     /// this potential global function is defined
     pub ns_id: NsId,
@@ -173,42 +341,21 @@ pub fn analyze<F: Borrow<library::Function>>(
 ) -> Vec<Info> {
     let mut funcs = Vec::new();
 
-    'func: for func in functions {
+    for func in functions {
         let func = func.borrow();
         let configured_functions = obj.functions.matched(&func.name);
-        let mut status = obj.status;
-        for f in &configured_functions {
-            match f.status {
-                GStatus::Ignore => continue 'func,
-                GStatus::Manual => {
-                    status = GStatus::Manual;
-                    break;
-                }
-                GStatus::Generate => (),
-            }
-        }
-
-        if env.is_totally_deprecated(
-            Some(type_tid.unwrap_or_default().ns_id),
-            func.deprecated_version,
-        ) {
+        let Ok(status) = filter(env, func, functions, type_tid, obj, &configured_functions) else {
             continue;
-        }
-        let name = nameutil::mangle_keywords(&*func.name).into_owned();
+        };
+        let name = nameutil::mangle_keywords(
+            bound_name(env, func, functions, type_tid, obj),
+            env.config.edition,
+        )
+        .into_owned();
         let signature_params = Signature::new(func);
-        let mut not_version = None;
-        if func.kind == library::FunctionKind::Method {
-            if let Some(deps) = deps {
-                let (has, version) = signature_params.has_in_deps(env, &name, deps);
-                if has {
-                    if let Some(v) = version {
-                        if v > env.config.min_cfg_version {
-                            not_version = version;
-                        }
-                    }
-                }
-            }
-        }
+        let Ok(not_version) = inherited_version(env, func, &signature_params, &name, deps) else {
+            continue;
+        };
         if let Some(signatures) = signatures.as_mut() {
             signatures.insert(name.clone(), signature_params);
         }
@@ -230,9 +377,305 @@ pub fn analyze<F: Borrow<library::Function>>(
         funcs.push(info);
     }
 
+    bind_variadic_siblings(env, functions, type_tid, obj, imports, &mut funcs);
+
     funcs
 }
 
+/// Binds the variadic functions through their sibling taking an array
+/// (`foo_setv` or `foo_set_v`), under the name of the variadic function.
+///
+/// The `va_list` siblings (`foo_set_valist`) can't be called from Rust either.
+fn bind_variadic_siblings<F: Borrow<library::Function>>(
+    env: &Env,
+    functions: &[F],
+    type_tid: Option<library::TypeId>,
+    obj: &config::gobjects::GObject,
+    imports: &mut Imports,
+    infos: &mut [Info],
+) {
+    for func in functions.iter().map(Borrow::borrow) {
+        let Some(pos) = func.parameters.iter().position(|par| {
+            matches!(
+                env.library.type_(par.typ),
+                Type::Basic(library::Basic::VarArgs)
+            )
+        }) else {
+            continue;
+        };
+        let Some(c_identifier) = func.c_identifier.as_deref() else {
+            continue;
+        };
+        if obj
+            .functions
+            .matched(&func.name)
+            .iter()
+            .any(|f| !f.status.need_generate())
+        {
+            continue;
+        }
+
+        let is_sibling = |info: &Info| {
+            ["v", "_v"].iter().any(|suffix| {
+                info.func_name == format!("{}{suffix}", func.name)
+                    || info.glib_name == format!("{c_identifier}{suffix}")
+            })
+        };
+        let Some(sibling) = infos
+            .iter()
+            .position(|info| is_sibling(info) && info.status.need_generate() && !info.commented)
+        else {
+            continue;
+        };
+
+        let name = nameutil::mangle_keywords(&func.name, env.config.edition).into_owned();
+        if let Some(info) = infos
+            .iter()
+            .find(|info| info.codegen_name() == name && !info.commented)
+        {
+            if type_tid.is_none_or(|tid| tid.ns_id == MAIN_NAMESPACE) {
+                warn!(
+                    "`{}`: can't be bound through `{}`, `{}` already exists",
+                    func.name,
+                    infos[sibling].glib_name,
+                    info.codegen_name()
+                );
+            }
+            continue;
+        }
+
+        let key_values = key_values(env, func, pos, &infos[sibling], name.clone());
+        if key_values.is_some() {
+            imports.add(&nameutil::use_glib_type(env, "prelude::*"));
+        } else if obj
+            .functions
+            .matched(&infos[sibling].func_name)
+            .iter()
+            .all(|f| f.rename.is_none())
+        {
+            infos[sibling].new_name = Some(name);
+        } else {
+            continue;
+        }
+        infos[sibling].variadic = Some(Variadic {
+            glib_name: c_identifier.to_owned(),
+            key_values,
+        });
+        // Already bound through the sibling
+        for info in infos.iter_mut() {
+            if info.glib_name == c_identifier {
+                info.hidden = true;
+            }
+        }
+    }
+}
+
+/// The function a variadic function is bound through, if any.
+pub(super) fn variadic_sibling<'a>(
+    analyzed: &'a [Info],
+    func: &library::Function,
+) -> Option<&'a Info> {
+    analyzed.iter().find(|info| {
+        info.variadic
+            .as_ref()
+            .is_some_and(|variadic| func.c_identifier.as_ref() == Some(&variadic.glib_name))
+    })
+}
+
+/// Recognizes the NULL-terminated key/value arguments following a
+/// `first_*_name` parameter, like `g_object_set`, with a sibling taking the
+/// names and the `GValue`s as arrays.
+fn key_values(
+    env: &Env,
+    func: &library::Function,
+    varargs_pos: usize,
+    sibling: &Info,
+    name: String,
+) -> Option<KeyValues> {
+    let first = &func.parameters[varargs_pos.checked_sub(1)?];
+    let key = first.name.strip_prefix("first_")?;
+    if first.typ != library::TypeId::tid_utf8() {
+        return None;
+    }
+    let value_tid = env.library.find_type(0, "GObject.Value")?;
+    let is_array_of = |par: &&function_parameters::RustParameter, inner_tid| matches!(env.library.type_(par.typ), Type::CArray(tid) if *tid == inner_tid);
+    let rust_parameters = &sibling.parameters.rust_parameters;
+    let keys = rust_parameters
+        .iter()
+        .find(|par| is_array_of(par, library::TypeId::tid_utf8()))?;
+    let values = rust_parameters
+        .iter()
+        .find(|par| is_array_of(par, value_tid))?;
+
+    // The other parameters are passed as they are
+    let skip = usize::from(sibling.kind == library::FunctionKind::Method);
+    let others = rust_parameters
+        .iter()
+        .skip(skip)
+        .filter(|par| par.ind_c != keys.ind_c && par.ind_c != values.ind_c)
+        .map(|par| par.name.as_str());
+    let variadic_others = func.parameters[..varargs_pos - 1]
+        .iter()
+        .skip(skip)
+        .map(|par| nameutil::mangle_keywords(&*par.name, env.config.edition));
+    if !others.eq(variadic_others) {
+        return None;
+    }
+
+    let key = key.strip_suffix("_name").unwrap_or(key);
+    Some(KeyValues {
+        name,
+        pairs: plural(key),
+        keys: keys.name.clone(),
+        values: values.name.clone(),
+    })
+}
+
+/// English plural of a parameter name: `property` becomes `properties`, but
+/// `key` becomes `keys`.
+fn plural(word: &str) -> String {
+    match word.strip_suffix('y') {
+        Some(stem) if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) => format!("{stem}ies"),
+        _ => format!("{word}s"),
+    }
+}
+
+/// Decides if a function is analyzed, and with which status.
+pub fn filter<F: Borrow<library::Function>>(
+    env: &Env,
+    func: &library::Function,
+    functions: &[F],
+    type_tid: Option<library::TypeId>,
+    obj: &config::gobjects::GObject,
+    configured_functions: &[&config::functions::Function],
+) -> std::result::Result<GStatus, SkipReason> {
+    let mut status = obj.status;
+    for f in configured_functions {
+        match f.status {
+            GStatus::Ignore => {
+                return Err(SkipReason::IgnoredByConfig {
+                    rule: f.ident.to_string(),
+                })
+            }
+            GStatus::Manual => {
+                status = GStatus::Manual;
+                break;
+            }
+            GStatus::Generate => (),
+        }
+    }
+
+    if status.need_generate() {
+        match configured_functions
+            .iter()
+            .find(|f| f.introspectable.is_some())
+        {
+            Some(f) if f.introspectable == Some(false) => {
+                return Err(SkipReason::IntrospectableDisabledByConfig {
+                    rule: f.ident.to_string(),
+                })
+            }
+            Some(_) => (),
+            None => {
+                // The shadowing function is bound under our name instead
+                if let Some(shadowed_by) = func.shadowed_by.as_ref().filter(|shadowed_by| {
+                    functions.iter().any(|f| f.borrow().name == **shadowed_by)
+                }) {
+                    return Err(SkipReason::ShadowedBy(shadowed_by.clone()));
+                }
+                // Special functions are only used for trait implementations
+                if !func.introspectable && func.name.parse::<special_functions::Type>().is_err() {
+                    return Err(SkipReason::NotIntrospectable);
+                }
+            }
+        }
+    }
+
+    if env.is_totally_deprecated(
+        Some(type_tid.unwrap_or_default().ns_id),
+        func.deprecated_version,
+    ) {
+        return Err(SkipReason::TotallyDeprecated(
+            func.deprecated_version.unwrap(),
+        ));
+    }
+
+    Ok(status)
+}
+
+/// Why a function left out by the analysis isn't bound, for the reports.
+pub(super) fn dropped_reason<F: Borrow<library::Function>>(
+    env: &Env,
+    func: &library::Function,
+    functions: &[F],
+    type_tid: Option<library::TypeId>,
+    obj: &config::gobjects::GObject,
+    configured_functions: &[&config::functions::Function],
+) -> Option<SkipReason> {
+    if let Err(reason) = filter(env, func, functions, type_tid, obj, configured_functions) {
+        return Some(reason);
+    }
+    let tid = type_tid?;
+    if !matches!(env.type_(tid), Type::Class(_) | Type::Interface(_)) {
+        return None;
+    }
+    let name = nameutil::mangle_keywords(
+        bound_name(env, func, functions, type_tid, obj),
+        env.config.edition,
+    );
+    let deps = supertypes::dependencies(env, tid);
+    inherited_version(env, func, &Signature::new(func), &name, Some(&deps)).err()
+}
+
+/// Version from which a method is provided with the same signature by one of
+/// the types it depends on, it's then only generated for the older versions.
+fn inherited_version(
+    env: &Env,
+    func: &library::Function,
+    signature: &Signature,
+    name: &str,
+    deps: Option<&[library::TypeId]>,
+) -> std::result::Result<Option<Version>, SkipReason> {
+    let Some(deps) = deps.filter(|_| func.kind == library::FunctionKind::Method) else {
+        return Ok(None);
+    };
+    match signature.has_in_deps(env, name, deps) {
+        (false, _) => Ok(None),
+        (true, Some(version)) if version > env.config.min_cfg_version => Ok(Some(version)),
+        (true, version) => Err(SkipReason::VersionTooLow(version)),
+    }
+}
+
+/// Name a function is bound under: the one of the function it shadows, unless
+/// that function is generated too.
+fn bound_name<'a, F: Borrow<library::Function>>(
+    env: &Env,
+    func: &'a library::Function,
+    functions: &[F],
+    type_tid: Option<library::TypeId>,
+    obj: &config::gobjects::GObject,
+) -> &'a str {
+    match func.shadows {
+        Some(ref shadows)
+            if !functions.iter().map(Borrow::borrow).any(|f| {
+                f.name == *shadows
+                    && filter(
+                        env,
+                        f,
+                        functions,
+                        type_tid,
+                        obj,
+                        &obj.functions.matched(&f.name),
+                    )
+                    .is_ok()
+            }) =>
+        {
+            shadows
+        }
+        _ => &func.name,
+    }
+}
+
 fn fixup_gpointer_parameter(
     env: &Env,
     type_tid: library::TypeId,
@@ -316,6 +759,93 @@ fn find_callback_bound_to_destructor(
     false
 }
 
+/// Registers the user data and destroy notify parameters of all the callbacks
+/// up front, as they aren't necessarily placed after their callback.
+///
+/// The closures sharing a user data are boxed together and freed by a single
+/// destroy notify, so the ones with the `call` scope take the scope of the
+/// others in the group to get the same `'static` and `Send` bounds.
+fn link_callback_parameters(
+    env: &Env,
+    parameters: &mut Parameters,
+    params: &mut [library::Parameter],
+    cross_user_data_check: &mut HashMap<usize, usize>,
+    user_data_indexes: &mut HashSet<usize>,
+    skip_reasons: &mut Vec<SkipReason>,
+) {
+    let c_parameters = &parameters.c_parameters;
+    // The closure and destroy indexes don't take the instance parameter into
+    // account
+    let offset = usize::from(c_parameters.first().is_some_and(|p| p.instance_parameter));
+    let user_data_pos = |index: usize| {
+        Some(index + offset).filter(|pos| {
+            c_parameters
+                .get(*pos)
+                .is_some_and(|p| is_gpointer(&p.c_type))
+        })
+    };
+    let destroy_pos = |index: usize| {
+        Some(index + offset).filter(|pos| {
+            c_parameters
+                .get(*pos)
+                .is_some_and(|p| p.c_type == "GDestroyNotify")
+        })
+    };
+
+    // Key: user data position
+    // Value: positions of the callbacks using it
+    let mut groups = BTreeMap::<usize, Vec<usize>>::new();
+    let mut destroyed = HashSet::new();
+    for (pos, par) in c_parameters.iter().enumerate() {
+        if par.c_type == "GDestroyNotify" || !env.library.type_(par.typ).is_function() {
+            continue;
+        }
+        let Some(user_data) = par.user_data_index.and_then(user_data_pos) else {
+            continue;
+        };
+        match par.destroy_index.and_then(destroy_pos) {
+            Some(destroy) => {
+                cross_user_data_check.entry(destroy).or_insert(user_data);
+                destroyed.insert(user_data);
+            }
+            None => {
+                user_data_indexes.insert(user_data);
+            }
+        }
+        groups.entry(user_data).or_default().push(pos);
+    }
+
+    for (user_data, callbacks) in groups.into_iter().filter(|(_, c)| c.len() > 1) {
+        if callbacks
+            .iter()
+            .any(|pos| parameters.c_parameters[*pos].scope.is_async())
+        {
+            skip_reasons.push(SkipReason::unsupported_callback(
+                None,
+                "an async callback shares its user data with other callbacks",
+            ));
+            continue;
+        }
+        if callbacks
+            .iter()
+            .all(|pos| parameters.c_parameters[*pos].scope.is_call())
+        {
+            continue;
+        }
+        let scope = if destroyed.contains(&user_data) {
+            library::ParameterScope::Notified
+        } else {
+            library::ParameterScope::Forever
+        };
+        for pos in callbacks {
+            if parameters.c_parameters[pos].scope.is_call() {
+                parameters.c_parameters[pos].scope = scope;
+                params[pos].scope = scope;
+            }
+        }
+    }
+}
+
 fn analyze_callbacks(
     env: &Env,
     func: &library::Function,
@@ -332,10 +862,19 @@ fn analyze_callbacks(
     configured_functions: &[&config::functions::Function],
     disable_length_detect: bool,
     in_trait: bool,
-    commented: &mut bool,
+    skip_reasons: &mut Vec<SkipReason>,
     concurrency: library::Concurrency,
     type_tid: library::TypeId,
 ) {
+    link_callback_parameters(
+        env,
+        parameters,
+        params,
+        cross_user_data_check,
+        user_data_indexes,
+        skip_reasons,
+    );
+
     let mut to_replace = Vec::new();
     let mut to_remove = Vec::new();
 
@@ -408,7 +947,7 @@ fn analyze_callbacks(
                         env,
                         par,
                         &callback_info,
-                        commented,
+                        skip_reasons,
                         imports,
                         &c_parameters,
                         rust_type,
@@ -424,7 +963,10 @@ fn analyze_callbacks(
                                     "`{}`: Different destructors cannot share the same user data",
                                     func_name
                                 );
-                                *commented = true;
+                                skip_reasons.push(SkipReason::unsupported_callback(
+                                    Some(&par.name),
+                                    "its user data is shared with another destroy notify",
+                                ));
                             }
                             callback.destroy_index = destroy_index;
                         } else {
@@ -441,7 +983,7 @@ fn analyze_callbacks(
                     env,
                     par,
                     &callback_info,
-                    commented,
+                    skip_reasons,
                     imports,
                     &c_parameters,
                     rust_type,
@@ -458,7 +1000,10 @@ fn analyze_callbacks(
                             "`{}`: no user data point to the destroy callback",
                             func_name,
                         );
-                        *commented = true;
+                        skip_reasons.push(SkipReason::unsupported_callback(
+                            Some(&par.name),
+                            "no user data is destroyed by this destroy notify",
+                        ));
                     }
                     // We check if the user trampoline is there. If so, we change the destroy
                     // nullable value if needed.
@@ -471,13 +1016,14 @@ fn analyze_callbacks(
                     continue;
                 }
             }
-            if !*commented {
-                *commented |= RustType::builder(env, par.typ)
-                    .direction(par.direction)
-                    .scope(par.scope)
-                    .try_from_glib(&par.try_from_glib)
-                    .try_build_param()
-                    .is_err();
+            if RustType::builder(env, par.typ)
+                .direction(par.direction)
+                .scope(par.scope)
+                .try_from_glib(&par.try_from_glib)
+                .try_build_param()
+                .is_err()
+            {
+                skip_reasons.push(SkipReason::unsupported_type(env, Some(&par.name), par.typ));
             }
         }
         for (destroy_index, pos_in_destroys) in destructors_to_update {
@@ -496,13 +1042,15 @@ fn analyze_callbacks(
     }
 
     // Check for cross "user data".
-    if cross_user_data_check
+    let mut destroyed_user_data = HashSet::new();
+    if !cross_user_data_check
         .values()
-        .collect::<Vec<_>>()
-        .windows(2)
-        .any(|a| a[0] == a[1])
+        .all(|user_data| destroyed_user_data.insert(user_data))
     {
-        *commented = true;
+        skip_reasons.push(SkipReason::unsupported_callback(
+            None,
+            "different user data share the same destroy notify",
+        ));
         warn_main!(
             type_tid,
             "`{}`: Different user data share the same destructors",
@@ -541,7 +1089,10 @@ fn analyze_callbacks(
             "`{}`: this is supposed to be a callback function but no callback was found...",
             func.name
         );
-        *commented = true;
+        skip_reasons.push(SkipReason::unsupported_callback(
+            None,
+            "no callback could be analyzed",
+        ));
     }
 }
 
@@ -576,7 +1127,7 @@ fn analyze_function(
         _ => library::Concurrency::SendSync,
     };
 
-    let mut commented = false;
+    let mut skip_reasons = Vec::new();
     let mut bounds: Bounds = Default::default();
     let mut to_glib_extras = HashMap::<usize, String>::new();
     let mut used_types: Vec<String> = Vec::with_capacity(4);
@@ -600,7 +1151,10 @@ fn analyze_function(
             "Function \"{}\" with destroy callback without callbacks",
             func.name
         );
-        commented = true;
+        skip_reasons.push(SkipReason::unsupported_callback(
+            None,
+            "destroy notify without any callback",
+        ));
     }
 
     let mut new_name = configured_functions.iter().find_map(|f| f.rename.clone());
@@ -682,7 +1236,14 @@ fn analyze_function(
         &mut used_types,
         imports,
     );
-    commented |= ret.commented;
+    if ret.commented {
+        if let Some(ref par) = ret.parameter {
+            // Constructors return the type they're defined on, not the
+            // unsupported one
+            let typ = ret.base_tid.unwrap_or(par.lib_par.typ);
+            skip_reasons.push(SkipReason::unsupported_type(env, None, typ));
+        }
+    }
 
     let mut params = func.parameters.clone();
     let mut parameters = function_parameters::analyze(
@@ -699,7 +1260,7 @@ fn analyze_function(
         if let Type::Function(_) = env.library.type_(f.lib_par.typ) {
             if env.config.work_mode.is_normal() {
                 warn!("Function \"{}\" returns callback", func.name);
-                commented = true;
+                skip_reasons.push(SkipReason::ReturnsCallback);
             }
         }
     }
@@ -776,7 +1337,7 @@ fn analyze_function(
                     type_tid,
                     new_name.as_ref().unwrap_or(&name),
                     callback_info,
-                    &mut commented,
+                    &mut skip_reasons,
                     &mut trampoline,
                     no_future,
                     &mut async_future,
@@ -792,11 +1353,11 @@ fn analyze_function(
                         .try_build_param()
                         .is_err();
                 if type_error {
-                    commented = true;
+                    skip_reasons.push(SkipReason::unsupported_type(env, Some(&par.name), par.typ));
                 }
             }
-            if r#async && trampoline.is_none() {
-                commented = true;
+            if r#async && trampoline.is_none() && skip_reasons.is_empty() {
+                skip_reasons.push(SkipReason::UnsupportedAsync("no async callback found"));
             }
         } else {
             analyze_callbacks(
@@ -815,7 +1376,7 @@ fn analyze_function(
                 configured_functions,
                 disable_length_detect,
                 in_trait,
-                &mut commented,
+                &mut skip_reasons,
                 concurrency,
                 type_tid,
             );
@@ -831,10 +1392,10 @@ fn analyze_function(
                 false
             }
         };
-        if is_carray_with_direct_elements(env, par.typ)
+        if is_carray_with_value_elements(env, par.typ)
             && !parameters.transformations.iter().any(is_len_for_par)
         {
-            commented = true;
+            skip_reasons.push(SkipReason::MissingLengthParameter(par.name.clone()));
         }
     }
 
@@ -851,10 +1412,24 @@ fn analyze_function(
             "Function {} has unsupported outs",
             func.c_identifier.as_ref().unwrap_or(&func.name)
         );
-        commented = true;
+        skip_reasons.push(SkipReason::UnsupportedOutParameters);
     }
+    let commented = !skip_reasons.is_empty();
 
-    if r#async && status.need_generate() && !commented {
+    let callback_stream =
+        if configured_functions.iter().any(|f| f.stream) && status.need_generate() && !commented {
+            analyze_stream(
+                env,
+                type_tid,
+                new_name.as_ref().unwrap_or(&name),
+                &callbacks,
+                &destroys,
+            )
+        } else {
+            None
+        };
+
+    if (r#async || callback_stream.is_some()) && status.need_generate() && !commented {
         imports.add("std::boxed::Box as Box_");
         imports.add("std::pin::Pin");
 
@@ -940,10 +1515,13 @@ fn analyze_function(
         unsafe_,
         trampoline,
         async_future,
+        callback_stream,
+        variadic: None,
         callbacks,
         destroys,
         remove_params: cross_user_data_check.values().copied().collect::<Vec<_>>(),
         commented,
+        skip_reasons,
         hidden: false,
         ns_id,
         generate_doc,
@@ -952,11 +1530,27 @@ fn analyze_function(
     }
 }
 
-pub fn is_carray_with_direct_elements(env: &Env, typ: library::TypeId) -> bool {
+pub fn is_carray_with_value_elements(env: &Env, typ: library::TypeId) -> bool {
     match *env.library.type_(typ) {
         Type::CArray(inner_tid) => {
             use super::conversion_type::ConversionType;
             matches!(env.library.type_(inner_tid), Type::Basic(..) if ConversionType::of(env, inner_tid) == ConversionType::Direct)
+                || is_array_with_scalar_elements(env, typ)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the elements of the (fixed-size) C array are enumerations, flags or
+/// booleans, that have to be converted one by one.
+pub fn is_array_with_scalar_elements(env: &Env, typ: library::TypeId) -> bool {
+    match *env.library.type_(typ) {
+        Type::CArray(inner_tid) | Type::FixedArray(inner_tid, ..) => {
+            use super::conversion_type::ConversionType;
+            matches!(
+                env.library.type_(inner_tid),
+                Type::Basic(library::Basic::Boolean) | Type::Enumeration(_) | Type::Bitfield(_)
+            ) && ConversionType::of(env, inner_tid) == ConversionType::Scalar
         }
         _ => false,
     }
@@ -968,7 +1562,7 @@ fn analyze_async(
     type_tid: library::TypeId,
     codegen_name: &str,
     callback_info: Option<CallbackInfo>,
-    commented: &mut bool,
+    skip_reasons: &mut Vec<SkipReason>,
     trampoline: &mut Option<AsyncTrampoline>,
     no_future: bool,
     async_future: &mut Option<AsyncFuture>,
@@ -983,7 +1577,11 @@ fn analyze_async(
     }) = callback_info
     {
         // Checks for /*Ignored*/ or other error comments
-        *commented |= callback_type.contains("/*");
+        if callback_type.contains("/*") {
+            skip_reasons.push(SkipReason::UnsupportedAsync(
+                "the callback has unsupported types",
+            ));
+        }
         let func_name = func.c_identifier.as_ref().unwrap();
         let finish_func_name = if let Some(finish_func_name) = &func.finish_func {
             finish_func_name.to_string()
@@ -1008,8 +1606,9 @@ fn analyze_async(
 
             for param in &function.parameters {
                 let mut lib_par = param.clone();
-                if nameutil::needs_mangling(&param.name) {
-                    lib_par.name = nameutil::mangle_keywords(&*param.name).into_owned();
+                if nameutil::needs_mangling(&param.name, env.config.edition) {
+                    lib_par.name =
+                        nameutil::mangle_keywords(&*param.name, env.config.edition).into_owned();
                 }
                 let configured_parameters = configured_functions.matched_parameters(&lib_par.name);
                 output_params.push(analysis::Parameter::from_parameter(
@@ -1026,10 +1625,12 @@ fn analyze_async(
                  moment",
                 func.name
             );
-            *commented = true;
+            skip_reasons.push(SkipReason::UnsupportedAsync(
+                "callbacks and async parameters at the same time",
+            ));
             return false;
         }
-        if !*commented && success_parameters.is_empty() {
+        if skip_reasons.is_empty() && success_parameters.is_empty() {
             if success_parameters.is_empty() {
                 warn_main!(
                     type_tid,
@@ -1037,7 +1638,9 @@ fn analyze_async(
                     func.name
                 );
             }
-            *commented = true;
+            skip_reasons.push(SkipReason::UnsupportedAsync(
+                "missing success parameters for the future",
+            ));
             return false;
         }
         let is_method = func.kind == FunctionKind::Method;
@@ -1073,13 +1676,104 @@ fn analyze_async(
     }
 }
 
+fn analyze_stream(
+    env: &Env,
+    type_tid: library::TypeId,
+    codegen_name: &str,
+    callbacks: &[Trampoline],
+    destroys: &[Trampoline],
+) -> Option<CallbackStream> {
+    // The stream ends when the closure holding the sender is destroyed
+    let [callback] = callbacks else {
+        warn_main!(
+            type_tid,
+            "`{}`: a stream needs exactly one callback, {} found",
+            codegen_name,
+            callbacks.len()
+        );
+        return None;
+    };
+    if callback.scope != library::ParameterScope::Notified
+        || !destroys
+            .iter()
+            .any(|d| d.destroy_index == callback.destroy_index)
+    {
+        warn_main!(
+            type_tid,
+            "`{}`: a stream needs a callback with the `notified` scope and a destroy notify",
+            codegen_name
+        );
+        return None;
+    }
+    if callback.ret.c_type != "void" {
+        warn_main!(
+            type_tid,
+            "`{}`: a stream can't be generated for a callback returning a value",
+            codegen_name
+        );
+        return None;
+    }
+
+    let mut parameters = Vec::new();
+    let mut values = Vec::new();
+    let mut item_types = Vec::new();
+    for par in &callback.parameters.transformations {
+        if par.name == "this" || callback.parameters.c_parameters[par.ind_c].is_real_gpointer(env) {
+            continue;
+        }
+        let rust_par = &callback.parameters.rust_parameters[par.ind_rust];
+        let rust_type = RustType::builder(env, par.typ)
+            .direction(rust_par.direction)
+            .nullable(rust_par.nullable)
+            .try_build()
+            .ok()?
+            .into_string();
+        let is_gstring = RustType::try_new(env, par.typ)
+            .is_ok_and(|rust_type| nameutil::is_gstring(rust_type.as_str()));
+        // The closure gets borrowed values
+        let (item_type, value) = if is_gstring {
+            if *rust_par.nullable {
+                (
+                    "Option<String>".to_owned(),
+                    format!("{}.map(ToOwned::to_owned)", par.name),
+                )
+            } else {
+                ("String".to_owned(), format!("{}.to_owned()", par.name))
+            }
+        } else if par.typ.is_basic_type(env) {
+            (rust_type, par.name.clone())
+        } else if *rust_par.nullable {
+            (rust_type, format!("{}.cloned()", par.name))
+        } else {
+            (rust_type, format!("{}.clone()", par.name))
+        };
+        parameters.push(par.name.clone());
+        values.push(value);
+        item_types.push(item_type);
+    }
+
+    let item_type = if item_types.len() == 1 {
+        item_types.remove(0)
+    } else {
+        format!("({})", item_types.join(", "))
+    };
+    Some(CallbackStream {
+        name: format!("{codegen_name}_stream"),
+        callback: callback.name.clone(),
+        nullable: callback.nullable,
+        parameters,
+        values,
+        item_type,
+    })
+}
+
 fn analyze_callback(
     func_name: &str,
     type_tid: library::TypeId,
     env: &Env,
     par: &CParameter,
     callback_info: &Option<CallbackInfo>,
-    commented: &mut bool,
+    skip_reasons: &mut Vec<SkipReason>,
     imports: &mut Imports,
     c_parameters: &[(&CParameter, usize)],
     rust_type: &Type,
@@ -1098,7 +1792,10 @@ fn analyze_callback(
                                c_parameters.len());
                     return None;
                 } else if !is_gpointer(&c_parameters[user_data].0.c_type) {
-                    *commented = true;
+                    skip_reasons.push(SkipReason::unsupported_callback(
+                        Some(&par.name),
+                        "invalid user data parameter",
+                    ));
                     warn_main!(
                         type_tid,
                         "function `{}`'s callback `{}` has invalid user data",
@@ -1108,7 +1805,10 @@ fn analyze_callback(
                     return None;
                 }
             } else {
-                *commented = true;
+                skip_reasons.push(SkipReason::unsupported_callback(
+                    Some(&par.name),
+                    "no user data parameter",
+                ));
                 warn_main!(
                     type_tid,
                     "function `{}`'s callback `{}` without associated user data",
@@ -1130,7 +1830,10 @@ fn analyze_callback(
                     return None;
                 }
                 if c_parameters[destroy_index].0.c_type != "GDestroyNotify" {
-                    *commented = true;
+                    skip_reasons.push(SkipReason::unsupported_callback(
+                        Some(&par.name),
+                        "invalid destroy notify parameter",
+                    ));
                     warn_main!(
                         type_tid,
                         "function `{}`'s callback `{}` has invalid destroy callback",
@@ -1147,7 +1850,10 @@ fn analyze_callback(
         if par.c_type != "GDestroyNotify"
             && (func.parameters.is_empty() || !func.parameters.iter().any(|c| c.closure.is_some()))
         {
-            *commented = true;
+            skip_reasons.push(SkipReason::unsupported_callback(
+                Some(&par.name),
+                format!("the `{}` type has no user data parameter", par.c_type),
+            ));
             warn_main!(
                 type_tid,
                 "Closure type `{}` doesn't provide user data for function {}",
@@ -1164,14 +1870,18 @@ fn analyze_callback(
             &[],
             callback_parameters_config,
         );
-        if par.c_type != "GDestroyNotify" && !*commented {
-            *commented |= func.parameters.iter().any(|p| {
-                if p.closure.is_none() {
-                    crate::analysis::trampolines::type_error(env, p).is_some()
-                } else {
-                    false
+        if par.c_type != "GDestroyNotify" {
+            for p in &func.parameters {
+                if p.closure.is_some() {
+                    continue;
                 }
-            });
+                if let Some(error) = crate::analysis::trampolines::type_error(env, p) {
+                    skip_reasons.push(SkipReason::unsupported_callback(
+                        Some(&par.name),
+                        format!("parameter `{}`: {error}", p.name),
+                    ));
+                }
+            }
         }
         for p in &parameters.rust_parameters {
             if let Ok(rust_type) = RustType::builder(env, p.typ)
@@ -1201,7 +1911,10 @@ fn analyze_callback(
                 func.name,
                 user_data_index
             );
-            *commented = true;
+            skip_reasons.push(SkipReason::unsupported_callback(
+                Some(&par.name),
+                "invalid user data index",
+            ));
             None
         } else if match par.destroy_index {
             Some(destroy_index) => c_parameters.len() <= destroy_index,
@@ -1213,10 +1926,13 @@ fn analyze_callback(
                 func.name,
                 par.destroy_index.unwrap()
             );
-            *commented = true;
+            skip_reasons.push(SkipReason::unsupported_callback(
+                Some(&par.name),
+                "invalid destroy notify index",
+            ));
             None
         } else {
-            if !*commented {
+            if skip_reasons.is_empty() {
                 for import in imports_to_add {
                     imports.add_used_type(&import);
                 }
@@ -1330,4 +2046,30 @@ mod tests {
         );
         assert_eq!("g_bus_get_finish", &finish_function_name("g_bus_get"));
     }
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(plural("property"), "properties");
+        assert_eq!(plural("key"), "keys");
+        assert_eq!(plural("day"), "days");
+        assert_eq!(plural("attribute"), "attributes");
+    }
+
+    #[test]
+    fn skip_reason_display() {
+        let reason = SkipReason::UnsupportedType {
+            parameter: Some("data".into()),
+            type_name: "Gio.Foo".into(),
+        };
+        assert_eq!(
+            reason.to_string(),
+            "parameter `data` has unsupported type `Gio.Foo`"
+        );
+        assert!(!reason.is_dropped());
+        let reason = SkipReason::IgnoredByConfig {
+            rule: "set_.*".into(),
+        };
+        assert_eq!(reason.to_string(), "ignored by the `set_.*` rule");
+        assert!(reason.is_dropped());
+    }
 }
@@ -16,7 +16,9 @@ pub mod class_hierarchy;
 pub mod constants;
 pub mod conversion_type;
 pub mod enums;
+pub mod explain;
 pub mod ffi_type;
+pub mod fields;
 pub mod flags;
 pub mod function_parameters;
 pub use function_parameters::Parameter;
@@ -25,6 +27,7 @@ pub mod general;
 pub mod imports;
 pub mod info_base;
 pub mod namespaces;
+pub mod not_bound;
 pub mod object;
 pub mod out_parameters;
 mod override_string_type;
@@ -44,11 +47,14 @@ pub mod trampoline_parameters;
 pub mod trampolines;
 pub mod try_from_glib;
 pub mod types;
+pub mod union;
+pub mod virtual_methods;
 
 #[derive(Debug, Default)]
 pub struct Analysis {
     pub objects: BTreeMap<String, object::Info>,
     pub records: BTreeMap<String, record::Info>,
+    pub unions: BTreeMap<String, union::Info>,
     pub global_functions: Option<info_base::InfoBase>,
     pub constants: Vec<constants::Info>,
 
@@ -342,6 +348,11 @@ fn analyze(env: &mut Env, tid: TypeId, deps: &[TypeId]) {
                 env.analysis.records.insert(full_name, info);
             }
         }
+        Type::Union(_) => {
+            if let Some(info) = union::new(env, obj) {
+                env.analysis.unions.insert(full_name, info);
+            }
+        }
         _ => {}
     }
 }
@@ -0,0 +1,454 @@
+//! Machine-readable counterpart of [`Library::show_non_bound_types`], listing
+//! the binding status of every type of the main namespace and of its members.
+//!
+//! [`Library::show_non_bound_types`]: crate::library::Library::show_non_bound_types
+
+use serde::Serialize;
+
+use super::{
+    functions::{self, SkipReason},
+    signals, virtual_methods,
+};
+use crate::{
+    config::{
+        gobjects::{GObject, GStatus},
+        matchable::Matchable,
+    },
+    env::Env,
+    library::{self, Type, TypeId, MAIN_NAMESPACE},
+    version::Version,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Generated,
+    Manual,
+    Ignored,
+    NotBound,
+    /// Generated commented out because of unsupported types.
+    Commented,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub c_identifier: Option<String>,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Entry {
+    fn new(name: &str, status: Status, reason: Option<String>) -> Self {
+        Self {
+            name: name.to_owned(),
+            c_identifier: None,
+            status,
+            reason,
+        }
+    }
+}
+
+/// The members are only listed for the types that were analyzed.
+#[derive(Debug, Serialize)]
+pub struct TypeEntry {
+    pub name: String,
+    pub kind: &'static str,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<Entry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<Entry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signals: Vec<Entry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub virtual_methods: Vec<Entry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub library: String,
+    pub version: String,
+    pub types: Vec<TypeEntry>,
+    pub functions: Vec<Entry>,
+}
+
+pub fn report(env: &Env) -> Report {
+    let ns = env.library.namespace(MAIN_NAMESPACE);
+
+    let types = ns
+        .types
+        .iter()
+        .enumerate()
+        .filter_map(|(id, typ)| {
+            let typ = typ.as_ref()?;
+            let tid = TypeId {
+                ns_id: MAIN_NAMESPACE,
+                id: id as u32,
+            };
+            type_entry(env, &format!("{}.{}", ns.name, typ.get_name()), tid, typ)
+        })
+        .collect();
+
+    let global_functions = ns
+        .functions
+        .iter()
+        .filter(|f| f.kind == library::FunctionKind::Global)
+        .collect::<Vec<_>>();
+    let functions = match env.config.objects.get(&format!("{}.*", ns.name)) {
+        Some(obj) if obj.status.need_generate() => {
+            let analyzed = env
+                .analysis
+                .global_functions
+                .as_ref()
+                .map_or(&[][..], |info| &info.functions);
+            function_entries(env, &global_functions, None, analyzed, obj)
+        }
+        obj => global_functions
+            .iter()
+            .map(|func| {
+                let (status, reason) = unanalyzed_type_status(obj);
+                function_entry(func, status, reason)
+            })
+            .collect(),
+    };
+
+    Report {
+        library: env.config.library_name.clone(),
+        version: env.config.library_version.clone(),
+        types,
+        functions,
+    }
+}
+
+fn type_entry(env: &Env, full_name: &str, tid: TypeId, typ: &Type) -> Option<TypeEntry> {
+    let kind = match typ {
+        Type::Class(_) => "class",
+        Type::Interface(_) => "interface",
+        // Class structures are bound along with their type
+        Type::Record(record) if record.gtype_struct_for.is_some() => return None,
+        Type::Record(_) => "record",
+        Type::Union(_) => "union",
+        Type::Enumeration(_) => "enumeration",
+        Type::Bitfield(_) => "bitfield",
+        _ => return None,
+    };
+    let obj = env.config.objects.get(full_name);
+
+    let mut entry = TypeEntry {
+        name: full_name.to_owned(),
+        kind,
+        status: Status::NotBound,
+        reason: None,
+        functions: Vec::new(),
+        properties: Vec::new(),
+        signals: Vec::new(),
+        virtual_methods: Vec::new(),
+    };
+
+    let Some(obj) = obj.filter(|obj| obj.status.need_generate()) else {
+        (entry.status, entry.reason) = unanalyzed_type_status(obj);
+        if entry.status == Status::NotBound {
+            if let Some(version) = typ.get_deprecated_version() {
+                entry.reason = Some(format!("deprecated in {version}"));
+            }
+        }
+        return Some(entry);
+    };
+
+    let Some(analyzed_functions) = analyzed_functions(env, full_name, typ) else {
+        entry.reason = Some("analysis failed".to_owned());
+        return Some(entry);
+    };
+    entry.status = Status::Generated;
+    entry.functions = function_entries(
+        env,
+        &typ.functions().iter().collect::<Vec<_>>(),
+        Some(tid),
+        analyzed_functions,
+        obj,
+    );
+
+    let (properties, signals, virtual_methods) = match typ {
+        Type::Class(klass) => (&klass.properties, &klass.signals, &klass.virtual_methods),
+        Type::Interface(iface) => (&iface.properties, &iface.signals, &iface.virtual_methods),
+        _ => return Some(entry),
+    };
+    let Some(info) = env.analysis.objects.get(full_name) else {
+        return Some(entry);
+    };
+    entry.properties = properties
+        .iter()
+        .map(|prop| property_entry(env, prop, info, obj))
+        .collect();
+    entry.signals = signals
+        .iter()
+        .map(|signal| signal_entry(env, signal, &info.signals, obj))
+        .collect();
+    entry.virtual_methods = virtual_methods
+        .iter()
+        .map(|func| virtual_method_entry(env, func, info.subclass.as_ref(), obj))
+        .collect();
+
+    Some(entry)
+}
+
+/// Analysis of the functions of a type in the `generate` list.
+pub(super) fn analyzed_functions<'a>(
+    env: &'a Env,
+    full_name: &str,
+    typ: &Type,
+) -> Option<&'a [functions::Info]> {
+    match typ {
+        Type::Class(_) | Type::Interface(_) => env
+            .analysis
+            .objects
+            .get(full_name)
+            .map(|info| &info.functions[..]),
+        Type::Record(_) => env
+            .analysis
+            .records
+            .get(full_name)
+            .map(|info| &info.functions[..]),
+        Type::Union(_) => env
+            .analysis
+            .unions
+            .get(full_name)
+            .map(|info| &info.functions[..]),
+        Type::Enumeration(_) => env
+            .analysis
+            .enumerations
+            .iter()
+            .find(|info| info.full_name == full_name)
+            .map(|info| &info.functions[..]),
+        Type::Bitfield(_) => env
+            .analysis
+            .flags
+            .iter()
+            .find(|info| info.full_name == full_name)
+            .map(|info| &info.functions[..]),
+        _ => None,
+    }
+}
+
+/// Status of a type (or of the global functions) that isn't analyzed.
+fn unanalyzed_type_status(obj: Option<&GObject>) -> (Status, Option<String>) {
+    match obj.map(|obj| obj.status) {
+        Some(GStatus::Manual) => (Status::Manual, None),
+        Some(GStatus::Ignore) => (Status::Ignored, None),
+        Some(GStatus::Generate) | None => (
+            Status::NotBound,
+            Some("not in the `generate` list".to_owned()),
+        ),
+    }
+}
+
+/// Why an item the analysis skipped isn't bound.
+fn skipped_status(
+    env: &Env,
+    configured_status: impl IntoIterator<Item = GStatus>,
+    introspectable: bool,
+    deprecated_version: Option<Version>,
+) -> (Status, Option<String>) {
+    if configured_status.into_iter().any(GStatus::ignored) {
+        (Status::Ignored, None)
+    } else if !introspectable {
+        (Status::NotBound, Some("not introspectable".to_owned()))
+    } else if env.is_totally_deprecated(Some(MAIN_NAMESPACE), deprecated_version) {
+        (
+            Status::NotBound,
+            Some(format!("deprecated in {}", deprecated_version.unwrap())),
+        )
+    } else {
+        (Status::NotBound, None)
+    }
+}
+
+fn function_entry(func: &library::Function, status: Status, reason: Option<String>) -> Entry {
+    Entry {
+        c_identifier: func.c_identifier.clone(),
+        ..Entry::new(&func.name, status, reason)
+    }
+}
+
+fn function_entries(
+    env: &Env,
+    functions: &[&library::Function],
+    type_tid: Option<TypeId>,
+    analyzed: &[functions::Info],
+    obj: &GObject,
+) -> Vec<Entry> {
+    functions
+        .iter()
+        .map(|func| {
+            if let Some(sibling) = functions::variadic_sibling(analyzed, func) {
+                return function_entry(
+                    func,
+                    Status::Generated,
+                    Some(format!("bound through `{}`", sibling.glib_name)),
+                );
+            }
+            let info = analyzed
+                .iter()
+                .find(|info| func.c_identifier.as_ref() == Some(&info.glib_name));
+            let (status, reason) = match info {
+                Some(info) if info.status.manual() => (Status::Manual, None),
+                Some(info) if info.commented => (
+                    Status::Commented,
+                    Some(
+                        info.skip_reasons
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    ),
+                ),
+                Some(info) if info.hidden => (
+                    Status::Generated,
+                    Some("used for trait implementations".to_owned()),
+                ),
+                Some(info) if info.is_async_finish(env) => (
+                    Status::Generated,
+                    Some("used by the async function".to_owned()),
+                ),
+                Some(_) => (Status::Generated, None),
+                None => {
+                    let configured_functions = obj.functions.matched(&func.name);
+                    match functions::dropped_reason(
+                        env,
+                        func,
+                        functions,
+                        type_tid,
+                        obj,
+                        &configured_functions,
+                    ) {
+                        Some(reason @ SkipReason::IgnoredByConfig { .. }) => {
+                            (Status::Ignored, Some(reason.to_string()))
+                        }
+                        Some(reason) => (Status::NotBound, Some(reason.to_string())),
+                        None => (Status::NotBound, None),
+                    }
+                }
+            };
+            function_entry(func, status, reason)
+        })
+        .collect()
+}
+
+fn property_entry(
+    env: &Env,
+    prop: &library::Property,
+    info: &super::object::Info,
+    obj: &GObject,
+) -> Entry {
+    let notify_name = format!("notify::{}", prop.name);
+    if info.properties.iter().any(|p| p.name == prop.name)
+        || info
+            .notify_signals
+            .iter()
+            .any(|s| s.signal_name == notify_name)
+    {
+        return Entry::new(&prop.name, Status::Generated, None);
+    }
+    let configured_status = obj
+        .properties
+        .matched(&prop.name)
+        .into_iter()
+        .map(|p| p.status);
+    let (status, reason) = skipped_status(env, configured_status, true, prop.deprecated_version);
+    let reason = reason.or_else(|| {
+        (status == Status::NotBound)
+            .then(|| "covered by methods or by a parent type's property".to_owned())
+    });
+    Entry::new(&prop.name, status, reason)
+}
+
+fn signal_entry(
+    env: &Env,
+    signal: &library::Signal,
+    analyzed: &[signals::Info],
+    obj: &GObject,
+) -> Entry {
+    let (status, reason) = match analyzed.iter().find(|s| s.signal_name == signal.name) {
+        Some(info) => match info.trampoline {
+            Ok(_) => (Status::Generated, None),
+            Err(ref errors) => (Status::Commented, Some(errors.join(", "))),
+        },
+        None => {
+            let configured_status = obj
+                .signals
+                .matched(&signal.name)
+                .into_iter()
+                .map(|s| s.status);
+            skipped_status(env, configured_status, true, signal.deprecated_version)
+        }
+    };
+    Entry::new(&signal.name, status, reason)
+}
+
+fn virtual_method_entry(
+    env: &Env,
+    func: &library::Function,
+    subclass: Option<&virtual_methods::Subclass>,
+    obj: &GObject,
+) -> Entry {
+    let Some(subclass) = subclass else {
+        return Entry::new(
+            &func.name,
+            Status::NotBound,
+            Some("subclassing support isn't generated".to_owned()),
+        );
+    };
+    let (status, reason) = match subclass
+        .virtual_methods
+        .iter()
+        .find(|vfunc| vfunc.vfunc_name == func.name)
+    {
+        Some(vfunc) if vfunc.status.manual() => (Status::Manual, None),
+        Some(vfunc) => match vfunc.trampoline {
+            Ok(_) => (Status::Generated, None),
+            Err(ref errors) => (Status::Commented, Some(errors.join(", "))),
+        },
+        None => {
+            let configured_status = obj
+                .virtual_methods
+                .matched(&func.name)
+                .into_iter()
+                .map(|f| f.status);
+            skipped_status(
+                env,
+                configured_status,
+                func.introspectable,
+                func.deprecated_version,
+            )
+        }
+    };
+    Entry::new(&func.name, status, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_serialization() {
+        let entry = Entry {
+            c_identifier: Some("test_widget_show".to_owned()),
+            ..Entry::new("show", Status::NotBound, None)
+        };
+        assert_eq!(
+            serde_json::to_string(&entry).unwrap(),
+            r#"{"name":"show","c_identifier":"test_widget_show","status":"not_bound"}"#
+        );
+
+        let entry = Entry::new("changed", Status::Commented, Some("reason".to_owned()));
+        assert_eq!(
+            serde_json::to_string(&entry).unwrap(),
+            r#"{"name":"changed","status":"commented","reason":"reason"}"#
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::{borrow::Cow, ops::Deref};
 
-use log::info;
+use log::{info, warn};
 
 use super::{
     child_properties::ChildProperties, imports::Imports, info_base::InfoBase,
@@ -41,6 +41,7 @@ pub struct Info {
     pub has_constructors: bool,
     pub has_functions: bool,
     pub virtual_methods: Vec<functions::Info>,
+    pub subclass: Option<virtual_methods::Subclass>,
     pub signals: Vec<signals::Info>,
     pub notify_signals: Vec<signals::Info>,
     pub properties: Vec<properties::Property>,
@@ -88,8 +89,8 @@ impl Info {
         self.generate_trait
     }
 
-    pub fn has_action_signals(&self) -> bool {
-        self.signals.iter().any(|s| s.action_emit_name.is_some())
+    pub fn has_emit_signals(&self) -> bool {
+        self.signals.iter().any(|s| s.emit_name.is_some())
     }
 
     /// Returns the location of the function within this object
@@ -208,8 +209,8 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
 
     let mut signatures = Signatures::with_capacity(klass.functions.len());
 
-    // As we don't generate virtual methods yet, we don't pass imports here
-    // it would need to be fixed once work in generating virtual methods is done
+    // Virtual methods are not part of the object module, the generated
+    // subclass module collects its own imports
     let virtual_methods = functions::analyze(
         env,
         &klass.virtual_methods,
@@ -274,6 +275,27 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
     let builder_properties =
         class_builder::analyze(env, &klass.properties, class_tid, obj, &mut imports);
 
+    let subclass = if final_type || is_fundamental {
+        if obj.generate_subclass {
+            warn!(
+                "Cannot generate subclassing support for final or fundamental type `{}`",
+                full_name
+            );
+        }
+        None
+    } else {
+        subclass(
+            env,
+            obj,
+            class_tid,
+            klass.parent,
+            klass.type_struct.as_deref(),
+            klass.c_class_type.as_deref(),
+            &klass.virtual_methods,
+            &klass.signals,
+        )
+    };
+
     let child_properties =
         child_properties::analyze(env, obj.child_properties.as_ref(), class_tid, &mut imports);
 
@@ -346,6 +368,7 @@ pub fn class(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<Info>
         has_constructors,
         has_functions,
         virtual_methods,
+        subclass,
         signals,
         notify_signals,
         properties,
@@ -433,6 +456,22 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
         &functions,
     );
 
+    let parent_tid = iface
+        .prerequisites
+        .iter()
+        .copied()
+        .find(|&tid| matches!(env.type_(tid), Type::Class(_)));
+    let subclass = subclass(
+        env,
+        obj,
+        iface_tid,
+        parent_tid,
+        iface.type_struct.as_deref(),
+        iface.c_class_type.as_deref(),
+        &iface.virtual_methods,
+        &iface.signals,
+    );
+
     let base = InfoBase {
         full_name,
         type_id: iface_tid,
@@ -460,6 +499,7 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
         generate_trait: true,
         trait_name,
         has_functions,
+        subclass,
         signals,
         notify_signals,
         properties,
@@ -469,3 +509,42 @@ pub fn interface(env: &Env, obj: &GObject, deps: &[library::TypeId]) -> Option<I
 
     Some(info)
 }
+
+fn subclass(
+    env: &Env,
+    obj: &GObject,
+    type_tid: library::TypeId,
+    parent_tid: Option<library::TypeId>,
+    type_struct: Option<&str>,
+    c_class_type: Option<&str>,
+    virtual_methods: &[library::Function],
+    signals: &[library::Signal],
+) -> Option<virtual_methods::Subclass> {
+    if !obj.generate_subclass {
+        return None;
+    }
+    let Some(c_class_type) = c_class_type else {
+        warn!(
+            "Cannot generate subclassing support for `{}` without a class structure",
+            obj.name
+        );
+        return None;
+    };
+    let class_fields = type_struct
+        .and_then(|name| env.library.find_type(type_tid.ns_id, name))
+        .and_then(|tid| match env.type_(tid) {
+            Type::Record(record) => Some(&record.fields[..]),
+            _ => None,
+        })
+        .unwrap_or_default();
+    Some(virtual_methods::analyze_subclass(
+        env,
+        virtual_methods,
+        signals,
+        type_tid,
+        parent_tid,
+        c_class_type,
+        class_fields,
+        obj,
+    ))
+}
@@ -5,7 +5,7 @@ use log::error;
 use crate::{
     analysis::{
         self, conversion_type::ConversionType, function_parameters::CParameter,
-        functions::is_carray_with_direct_elements, return_value, rust_type::RustType,
+        functions::is_carray_with_value_elements, return_value, rust_type::RustType,
     },
     config::{self, parameter_matchable::ParameterMatchable},
     env::Env,
@@ -82,7 +82,8 @@ pub fn analyze(
         }
         if can_as_return(env, lib_par) {
             let mut lib_par = lib_par.clone();
-            lib_par.name = nameutil::mangle_keywords(&lib_par.name).into_owned();
+            lib_par.name =
+                nameutil::mangle_keywords(&lib_par.name, env.config.edition).into_owned();
             let configured_parameters = configured_functions.matched_parameters(&lib_par.name);
             let mut out =
                 analysis::Parameter::from_parameter(env, &lib_par, &configured_parameters);
@@ -131,7 +132,11 @@ pub fn can_as_return(env: &Env, par: &library::Parameter) -> bool {
         Direct | Scalar | Option | Result { .. } => true,
         Pointer => {
             // Disallow Basic arrays without length
-            if is_carray_with_direct_elements(env, par.typ) && par.array_length.is_none() {
+            if is_carray_with_value_elements(env, par.typ) && par.array_length.is_none() {
+                return false;
+            }
+            // Fixed-size arrays are only filled in place
+            if matches!(env.library.type_(par.typ), Type::FixedArray(..)) && !par.caller_allocates {
                 return false;
             }
 
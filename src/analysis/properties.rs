@@ -32,6 +32,7 @@ pub struct Property {
     pub set_bound: Option<PropertyBound>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
+    pub default_value: Option<String>,
 }
 
 pub fn analyze(
@@ -259,7 +260,7 @@ fn analyze_property(
 
         Some(Property {
             name: name.clone(),
-            var_name: nameutil::mangle_keywords(&*name_for_func).into_owned(),
+            var_name: nameutil::mangle_keywords(&*name_for_func, env.config.edition).into_owned(),
             typ: prop.typ,
             is_get: true,
             func_name: get_func_name,
@@ -271,6 +272,7 @@ fn analyze_property(
             bounds: Bounds::default(),
             version: getter_version,
             deprecated_version: prop.deprecated_version,
+            default_value: prop.default_value.clone(),
         })
     } else {
         None
@@ -312,7 +314,7 @@ fn analyze_property(
 
         Some(Property {
             name: name.clone(),
-            var_name: nameutil::mangle_keywords(&*name_for_func).into_owned(),
+            var_name: nameutil::mangle_keywords(&*name_for_func, env.config.edition).into_owned(),
             typ: prop.typ,
             is_get: false,
             func_name: set_func_name,
@@ -324,6 +326,7 @@ fn analyze_property(
             bounds: Bounds::default(),
             version: setter_version,
             deprecated_version: prop.deprecated_version,
+            default_value: prop.default_value.clone(),
         })
     } else {
         None
@@ -362,6 +365,11 @@ fn analyze_property(
                 is_action: false,
                 is_detailed: false, /* well, technically this *is* an instance of a detailed
                                      * signal, but we "pre-detailed" it */
+                // The flags `GObject::notify` is registered with
+                when: Some(library::SignalEmissionStage::First),
+                no_recurse: true,
+                no_hooks: true,
+                no_detail: false,
                 version: prop_version,
                 deprecated_version: prop.deprecated_version,
                 doc: None,
@@ -388,10 +396,11 @@ fn analyze_property(
                 connect_name: format!("connect_{name_for_func}_notify"),
                 signal_name: format!("notify::{name}"),
                 trampoline,
-                action_emit_name: None,
+                emit_name: None,
                 version: prop_version,
                 deprecated_version: prop.deprecated_version,
                 doc_hidden: false,
+                is_action: false,
                 is_detailed: false, // see above comment
                 generate_doc: obj.generate_doc,
             })
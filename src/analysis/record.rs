@@ -25,6 +25,7 @@ pub struct Info {
     pub init_function_expression: Option<String>,
     pub copy_into_function_expression: Option<String>,
     pub clear_function_expression: Option<String>,
+    pub fields: Vec<fields::Info>,
 }
 
 impl Deref for Info {
@@ -159,6 +160,15 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
 
     special_functions::analyze_imports(&specials, &mut imports);
 
+    let fields = fields::analyze(
+        env,
+        &record.fields,
+        obj,
+        boxed_inline,
+        &functions,
+        &mut imports,
+    );
+
     let glib_get_type = if let Some(ref glib_get_type) = record.glib_get_type {
         let configured_functions = obj.functions.matched("get_type");
         let get_type_version = configured_functions
@@ -219,6 +229,7 @@ pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
         init_function_expression: obj.init_function_expression.clone(),
         copy_into_function_expression: obj.copy_into_function_expression.clone(),
         clear_function_expression: obj.clear_function_expression.clone(),
+        fields,
     };
 
     Some(info)
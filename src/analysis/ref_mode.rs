@@ -58,7 +58,9 @@ impl RefMode {
             | List(..)
             | SList(..)
             | PtrArray(..)
-            | CArray(..) => {
+            | CArray(..)
+            | FixedArray(..)
+            | HashTable(..) => {
                 if direction == library::ParameterDirection::In {
                     Self::ByRef
                 } else {
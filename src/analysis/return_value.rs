@@ -10,6 +10,22 @@ use crate::{
     library::{self, Nullable, TypeId},
 };
 
+/// Returned buffer accessed without copying it, see `zero_copy`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZeroCopy {
+    /// `guint8*` with its length in an out parameter, handed over to a
+    /// `glib::Bytes`.
+    Buffer,
+    /// `guint8*` with its length in an out parameter, still owned by the
+    /// instance: returned as a `&[u8]` tied to `&self`.
+    BorrowedBuffer,
+    /// `GByteArray*`, or `GArray*` of bytes which has the same layout.
+    ByteArray,
+    /// `GArray*` of other numbers or of inline records, its elements are
+    /// handed over to a `glib::Slice`.
+    Array,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Info {
     pub parameter: Option<analysis::Parameter>,
@@ -17,6 +33,9 @@ pub struct Info {
     pub commented: bool,
     pub bool_return_is_error: Option<String>,
     pub nullable_return_is_error: Option<String>,
+    pub zero_copy: Option<ZeroCopy>,
+    /// Returns a reference tied to `&self` instead of a copy.
+    pub borrow: bool,
 }
 
 pub fn analyze(
@@ -33,11 +52,27 @@ pub fn analyze(
         .find_map(|f| f.ret.type_name.as_ref())
         .and_then(|typ| env.library.find_type(0, typ))
         .unwrap_or_else(|| override_string_type_return(env, func.ret.typ, configured_functions));
+    let zero_copy = configured_functions
+        .iter()
+        .any(|f| f.ret.zero_copy)
+        .then(|| analyze_zero_copy(env, func, typ))
+        .and_then(|res| {
+            res.map_err(|reason| {
+                error!(
+                    "Ignoring zero_copy configuration for function {}: {}",
+                    func.name, reason
+                );
+            })
+            .ok()
+        });
+    let typ = zero_copy.map_or(typ, |(_, typ)| typ);
+    let zero_copy = zero_copy.map(|(zero_copy, _)| zero_copy);
+
     let mut parameter = if typ == Default::default() {
         None
     } else {
         let mut nullable = func.ret.nullable;
-        if !obj.trust_return_value_nullability {
+        if !obj.trust_return_value_nullability && zero_copy.is_none() {
             // Since GIRs are bad at specifying return value nullability, assume
             // any returned pointer is nullable unless overridden by the config.
             if !*nullable && can_be_nullable_return(env, typ) {
@@ -102,6 +137,20 @@ pub fn analyze(
         }
     });
 
+    // The rules of a method also match the virtual method of the same name
+    let borrow = configured_functions.iter().any(|f| f.ret.borrow)
+        && func.kind != library::FunctionKind::VirtualMethod
+        && match check_borrow(env, func, typ, nullable_return_is_error.is_some()) {
+            Ok(()) => true,
+            Err(reason) => {
+                error!(
+                    "Ignoring borrow configuration for function {}: {}",
+                    func.name, reason
+                );
+                false
+            }
+        };
+
     let mut base_tid = None;
 
     if func.kind == library::FunctionKind::Constructor {
@@ -118,9 +167,14 @@ pub fn analyze(
         }
     }
 
+    // Only the elements are converted when handed over to a `glib::Slice`
+    let element_typ = match (zero_copy, env.library.type_(typ)) {
+        (Some(ZeroCopy::Array), library::Type::Array(inner_tid)) => *inner_tid,
+        _ => typ,
+    };
     let parameter = parameter.as_ref().map(|lib_par| {
         let par = analysis::Parameter::from_return_value(env, lib_par, configured_functions);
-        if let Ok(rust_type) = RustType::builder(env, typ)
+        if let Ok(rust_type) = RustType::builder(env, element_typ)
             .direction(par.lib_par.direction)
             .try_from_glib(&par.try_from_glib)
             .try_build()
@@ -128,7 +182,7 @@ pub fn analyze(
             used_types.extend(rust_type.into_used_types());
         }
 
-        commented = RustType::builder(env, typ)
+        commented = RustType::builder(env, element_typ)
             .direction(func.ret.direction)
             .try_from_glib(&par.try_from_glib)
             .try_build_param()
@@ -143,7 +197,123 @@ pub fn analyze(
         commented,
         bool_return_is_error: bool_return_error_message,
         nullable_return_is_error: nullable_return_error_message,
+        zero_copy,
+        borrow,
+    }
+}
+
+/// Only the instance can keep the returned value alive.
+fn check_borrow(
+    env: &Env,
+    func: &library::Function,
+    typ: TypeId,
+    nullable_return_is_error: bool,
+) -> Result<(), &'static str> {
+    use crate::library::{Basic::*, Type::*};
+
+    if !func
+        .parameters
+        .first()
+        .is_some_and(|par| par.instance_parameter)
+    {
+        return Err("there is no instance to borrow from");
+    }
+    if func.ret.transfer != library::Transfer::None {
+        return Err("the returned value is owned by the caller");
+    }
+    if func.throws
+        || func
+            .parameters
+            .iter()
+            .any(|par| par.direction == library::ParameterDirection::Out)
+        || nullable_return_is_error
+    {
+        return Err("only plain return values can be borrowed");
+    }
+    match env.library.type_(typ) {
+        // The strings of tables are borrowed, their other elements copied
+        Basic(Utf8) | HashTable(..) => Ok(()),
+        // Other records are only pointers to the C structures
+        Record(_)
+            if env
+                .config
+                .objects
+                .get(&typ.full_name(&env.library))
+                .is_some_and(|obj| obj.boxed_inline) =>
+        {
+            Ok(())
+        }
+        _ => Err("only strings, hash tables and inline records can be borrowed"),
+    }
+}
+
+/// Checks that the returned buffer can be taken over or borrowed, returns how
+/// along with the type returned: `GLib.Bytes` unless the buffer is borrowed or
+/// handed over to a `glib::Slice`.
+fn analyze_zero_copy(
+    env: &Env,
+    func: &library::Function,
+    typ: TypeId,
+) -> Result<(ZeroCopy, TypeId), &'static str> {
+    use crate::library::{Basic::*, Type::*};
+
+    let is_byte = |tid| matches!(env.library.type_(tid), Basic(Int8 | UInt8));
+    // The types `glib::Slice` can hold as they are
+    let is_slice_element = |tid: TypeId| match env.library.type_(tid) {
+        Basic(
+            Int16 | UInt16 | Int32 | UInt32 | Int64 | UInt64 | Int | UInt | Long | ULong | Float
+            | Double,
+        ) => true,
+        Record(_) => env
+            .config
+            .objects
+            .get(&tid.full_name(&env.library))
+            .is_some_and(|obj| obj.boxed_inline),
+        _ => false,
+    };
+    let zero_copy = match env.library.type_(typ) {
+        CArray(inner_tid) if is_byte(*inner_tid) => {
+            let has_out_length = func
+                .ret
+                .array_length
+                .and_then(|pos| func.parameters.get(pos as usize))
+                .is_some_and(|par| par.direction == library::ParameterDirection::Out);
+            if !has_out_length {
+                return Err("the length of the buffer isn't returned");
+            }
+            if func.ret.transfer != library::Transfer::None {
+                ZeroCopy::Buffer
+            } else if func
+                .parameters
+                .first()
+                .is_some_and(|par| par.instance_parameter)
+            {
+                ZeroCopy::BorrowedBuffer
+            } else {
+                return Err("the buffer isn't owned by the caller nor by an instance");
+            }
+        }
+        Array(inner_tid) if is_byte(*inner_tid) => ZeroCopy::ByteArray,
+        Array(inner_tid) if is_slice_element(*inner_tid) => ZeroCopy::Array,
+        Record(_) if typ.full_name(&env.library) == "GLib.ByteArray" => ZeroCopy::ByteArray,
+        _ => return Err("only arrays of bytes, numbers or inline records are supported"),
+    };
+    if matches!(zero_copy, ZeroCopy::ByteArray | ZeroCopy::Array)
+        && func.ret.transfer == library::Transfer::None
+    {
+        return Err("the array isn't owned by the caller");
+    }
+    if *func.ret.nullable {
+        return Err("the buffer is nullable");
+    }
+    if matches!(zero_copy, ZeroCopy::BorrowedBuffer | ZeroCopy::Array) {
+        return Ok((zero_copy, typ));
     }
+    let bytes_tid = env
+        .library
+        .find_type(0, "GLib.Bytes")
+        .ok_or("`GLib.Bytes` isn't available")?;
+    Ok((zero_copy, bytes_tid))
 }
 
 fn can_be_nullable_return(env: &Env, type_id: library::TypeId) -> bool {
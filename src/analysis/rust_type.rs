@@ -182,6 +182,21 @@ impl MapAny<RustType> for Result {
     }
 }
 
+/// Whether the elements of a `GHashTable` can be converted one by one:
+/// strings, integers and booleans stored in the pointers, enumerations, flags
+/// and objects.
+pub fn is_hash_table_element(env: &Env, tid: library::TypeId) -> bool {
+    use crate::library::{Basic::*, Type::*};
+    matches!(
+        env.library.type_(tid),
+        Basic(Utf8 | Int | UInt | Int32 | UInt32 | Boolean)
+            | Enumeration(..)
+            | Bitfield(..)
+            | Class(..)
+            | Interface(..)
+    )
+}
+
 pub struct RustTypeBuilder<'env> {
     env: &'env Env,
     type_id: library::TypeId,
@@ -379,11 +394,15 @@ impl<'env> RustTypeBuilder<'env> {
                         })
                     })
             }
-            CArray(inner_tid)
-                if ConversionType::of(self.env, inner_tid) == ConversionType::Direct =>
+            CArray(inner_tid) | FixedArray(inner_tid, ..)
+                if matches!(
+                    ConversionType::of(self.env, inner_tid),
+                    ConversionType::Direct | ConversionType::Scalar
+                ) =>
             {
-                if let Basic(fund) = self.env.type_(inner_tid) {
-                    let array_type = match fund {
+                // Enumerations, flags and booleans are converted one by one
+                let element = match self.env.type_(inner_tid) {
+                    Basic(fund) => match fund {
                         Int8 => Some("i8"),
                         UInt8 => Some("u8"),
                         Int16 => Some("i16"),
@@ -398,21 +417,62 @@ impl<'env> RustTypeBuilder<'env> {
 
                         Float => Some("f32"),
                         Double => Some("f64"),
+                        Boolean => Some("bool"),
                         _ => Option::None,
-                    };
+                    }
+                    .map_or_else(
+                        || Err(TypeError::Unimplemented(type_.get_name())),
+                        |s| Ok(s.into()),
+                    ),
+                    Enumeration(..) | Bitfield(..) => RustType::try_new(self.env, inner_tid),
+                    _ => Err(TypeError::Unimplemented(type_.get_name())),
+                };
 
-                    if let Some(s) = array_type {
-                        skip_option = true;
-                        if self.ref_mode.is_ref() {
-                            Ok(format!("[{s}]").into())
-                        } else {
-                            Ok(format!("Vec<{s}>").into())
-                        }
-                    } else {
-                        Err(TypeError::Unimplemented(type_.get_name()))
+                skip_option = true;
+                element.map_any(|rust_type| {
+                    rust_type.alter_type(|typ| match *type_ {
+                        FixedArray(_, size, _) => format!("[{typ}; {size}]"),
+                        _ if self.ref_mode.is_ref() => format!("[{typ}]"),
+                        _ => format!("Vec<{typ}>"),
+                    })
+                })
+            }
+            HashTable(key_tid, value_tid) => {
+                skip_option = true;
+                // Converted element by element, the strings into owned ones
+                let element = |tid| match self.env.type_(tid) {
+                    Basic(Utf8) => Ok(RustType::from("String")),
+                    _ if is_hash_table_element(self.env, tid) => RustType::try_new(self.env, tid),
+                    typ => Err(TypeError::Unimplemented(
+                        RustType::try_new(self.env, tid)
+                            .map_or_else(|_| typ.get_name(), IntoString::into_string),
+                    )),
+                };
+                let name = |element: Result| match element {
+                    Ok(rust_type) => rust_type.into_string(),
+                    Err(
+                        TypeError::Ignored(s)
+                        | TypeError::Mismatch(s)
+                        | TypeError::Unimplemented(s),
+                    ) => s,
+                };
+                match (element(key_tid), element(value_tid)) {
+                    (Ok(key), Ok(value)) => {
+                        let type_name = format!(
+                            "std::collections::HashMap<{}, {}>",
+                            key.as_str(),
+                            value.as_str()
+                        );
+                        let mut used_types = vec!["std::collections::HashMap".to_owned()];
+                        used_types.extend(key.into_used_types());
+                        used_types.extend(value.into_used_types());
+                        Ok(RustType::new_with_uses(&type_name, &used_types))
                     }
-                } else {
-                    Err(TypeError::Unimplemented(type_.get_name()))
+                    (key, value) => Err(TypeError::Unimplemented(format!(
+                        "std::collections::HashMap<{}, {}>",
+                        name(key),
+                        name(value)
+                    ))),
                 }
             }
             Custom(library::Custom { ref name, .. }) => {
@@ -669,7 +729,7 @@ impl<'env> RustTypeBuilder<'env> {
                 _ => Err(TypeError::Unimplemented(into_inner(rust_type))),
             },
 
-            List(..) | SList(..) => match self.direction {
+            List(..) | SList(..) | HashTable(..) => match self.direction {
                 ParameterDirection::In | ParameterDirection::Return => rust_type,
                 _ => Err(TypeError::Unimplemented(into_inner(rust_type))),
             },
@@ -679,6 +739,11 @@ impl<'env> RustTypeBuilder<'env> {
                 }
                 _ => Err(TypeError::Unimplemented(into_inner(rust_type))),
             },
+            // Returned fixed-size arrays are only pointers to their first element
+            FixedArray(..) => match self.direction {
+                ParameterDirection::In | ParameterDirection::Out => rust_type,
+                _ => Err(TypeError::Unimplemented(into_inner(rust_type))),
+            },
             Function(ref func) if func.name == "AsyncReadyCallback" => {
                 Ok("AsyncReadyCallback".into())
             }
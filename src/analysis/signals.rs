@@ -12,11 +12,12 @@ use crate::{
 pub struct Info {
     pub connect_name: String,
     pub signal_name: String,
-    pub action_emit_name: Option<String>,
+    pub emit_name: Option<String>,
     pub trampoline: Result<Trampoline, Vec<String>>,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
     pub doc_hidden: bool,
+    pub is_action: bool,
     pub is_detailed: bool,
     pub generate_doc: bool,
 }
@@ -92,7 +93,13 @@ fn analyze_signal(
         version,
     );
 
-    let action_emit_name = if signal.is_action {
+    // Action signals are meant to be emitted by users, other signals only
+    // get an emitter when asked for.
+    let generate_emit = configured_signals
+        .iter()
+        .find_map(|f| f.generate_emit)
+        .unwrap_or(signal.is_action || obj.generate_emit_signals);
+    let emit_name = if generate_emit {
         imports.add("glib::prelude::*");
         Some(format!("emit_{}", nameutil::signal_to_snake(&signal.name)))
     } else {
@@ -111,11 +118,12 @@ fn analyze_signal(
         connect_name,
         signal_name: signal.name.clone(),
         trampoline,
-        action_emit_name,
+        emit_name,
         version,
         deprecated_version,
         doc_hidden,
-        is_detailed: signal.is_detailed,
+        is_action: signal.is_action,
+        is_detailed: signal.is_detailed && !signal.no_detail,
         generate_doc,
     }
 }
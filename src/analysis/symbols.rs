@@ -176,7 +176,7 @@ pub fn run(library: &Library, namespaces: &namespaces::Info) -> Info {
                         let symbol = Symbol {
                             crate_name: crate_name.cloned(),
                             owner_name: Some(name.clone()),
-                            name: func.name.clone(),
+                            name: func.shadows.as_ref().unwrap_or(&func.name).clone(),
                             ..Default::default()
                         };
                         info.insert(func.c_identifier.as_ref().unwrap(), symbol, None);
@@ -205,7 +205,24 @@ pub fn run(library: &Library, namespaces: &namespaces::Info) -> Info {
                         let symbol = Symbol {
                             crate_name: crate_name.cloned(),
                             owner_name: Some(name.clone()),
-                            name: func.name.clone(),
+                            name: func.shadows.as_ref().unwrap_or(&func.name).clone(),
+                            ..Default::default()
+                        };
+                        info.insert(func.c_identifier.as_ref().unwrap(), symbol, None);
+                    }
+                }
+                Type::Union(Union {
+                    name,
+                    c_type: Some(c_type),
+                    functions,
+                    ..
+                }) => {
+                    info.insert(c_type, symbol, Some(tid));
+                    for func in functions {
+                        let symbol = Symbol {
+                            crate_name: crate_name.cloned(),
+                            owner_name: Some(name.clone()),
+                            name: func.shadows.as_ref().unwrap_or(&func.name).clone(),
                             ..Default::default()
                         };
                         info.insert(func.c_identifier.as_ref().unwrap(), symbol, None);
@@ -136,7 +136,7 @@ pub fn analyze(
     parameters.transformations.push(transform);
 
     for par in signal_parameters {
-        let name = nameutil::mangle_keywords(&*par.name).into_owned();
+        let name = nameutil::mangle_keywords(&*par.name, env.config.edition).into_owned();
 
         let ref_mode = RefMode::without_unneeded_mut(env, par, false, false);
 
@@ -4,6 +4,7 @@ use super::{
     bounds::{BoundType, Bounds},
     conversion_type::ConversionType,
     ffi_type::used_ffi_type,
+    functions::is_array_with_scalar_elements,
     ref_mode::RefMode,
     rust_type::RustType,
     trampoline_parameters::{self, Parameters},
@@ -244,6 +245,11 @@ pub fn type_error(env: &Env, par: &library::Parameter) -> Option<&'static str> {
         Some("Empty ctype")
     } else if ConversionType::of(env, par.typ) == ConversionType::Unknown {
         Some("Unknown conversion")
+    } else if matches!(env.library.type_(par.typ), library::Type::FixedArray(..))
+        || is_array_with_scalar_elements(env, par.typ)
+    {
+        // The trampolines don't convert these arrays yet
+        Some("Unimplemented")
     } else {
         match RustType::try_new(env, par.typ) {
             Err(Ignored(_)) => Some("Ignored"),
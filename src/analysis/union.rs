@@ -0,0 +1,158 @@
+use std::ops::Deref;
+
+use log::info;
+
+use super::{imports::Imports, info_base::InfoBase, types::IsIncomplete, *};
+use crate::{
+    config::{
+        derives::{Derive, Derives},
+        gobjects::GObject,
+    },
+    env::Env,
+    library,
+    nameutil::*,
+    traits::*,
+};
+
+#[derive(Debug, Default)]
+pub struct Info {
+    pub base: InfoBase,
+    pub glib_get_type: String,
+    pub derives: Derives,
+    pub boxed_inline: bool,
+    pub init_function_expression: Option<String>,
+    pub copy_into_function_expression: Option<String>,
+    pub clear_function_expression: Option<String>,
+    pub fields: Vec<fields::Info>,
+    /// The field telling which of the other fields is valid, it's the only
+    /// one that can be read safely.
+    pub tag_field: Option<String>,
+}
+
+impl Deref for Info {
+    type Target = InfoBase;
+
+    fn deref(&self) -> &InfoBase {
+        &self.base
+    }
+}
+
+impl Info {
+    pub fn type_<'a>(&self, library: &'a library::Library) -> &'a library::Union {
+        let type_ = library
+            .type_(self.type_id)
+            .maybe_ref()
+            .unwrap_or_else(|| panic!("{} is not an union.", self.full_name));
+        type_
+    }
+}
+
+pub fn new(env: &Env, obj: &GObject) -> Option<Info> {
+    info!("Analyzing union {}", obj.name);
+    let full_name = obj.name.clone();
+
+    let union_tid = env.library.find_type(0, &full_name)?;
+
+    let type_ = env.type_(union_tid);
+
+    let name: String = split_namespace_name(&full_name).1.into();
+
+    let union: &library::Union = type_.maybe_ref()?;
+
+    let Some(glib_get_type) = union.glib_get_type.clone() else {
+        error!("Missing memory management functions for {}", full_name);
+        return None;
+    };
+    let boxed_inline = obj.boxed_inline;
+
+    let mut imports = Imports::with_defined(&env.library, &name);
+    imports.add("crate::ffi");
+
+    let mut functions = functions::analyze(
+        env,
+        &union.functions,
+        Some(union_tid),
+        false,
+        true,
+        obj,
+        &mut imports,
+        None,
+        None,
+    );
+    let specials = special_functions::extract(&mut functions, type_, obj);
+    special_functions::analyze_imports(&specials, &mut imports);
+
+    let derives = if let Some(ref derives) = obj.derives {
+        if boxed_inline
+            && !derives.is_empty()
+            && !derives
+                .iter()
+                .all(|ds| ds.names.is_empty() || ds.names.iter().all(|n| n == "Debug"))
+        {
+            panic!("Can't automatically derive traits other than `Debug` for BoxedInline unions");
+        }
+        derives.clone()
+    } else if !boxed_inline {
+        vec![Derive {
+            names: vec![
+                "Debug".into(),
+                "PartialEq".into(),
+                "Eq".into(),
+                "PartialOrd".into(),
+                "Ord".into(),
+                "Hash".into(),
+            ],
+            cfg_condition: None,
+        }]
+    } else {
+        vec![]
+    };
+
+    // The `-sys` crate only has an opaque type for incomplete unions. Setting
+    // a field would leave the tag out of sync
+    let fields = if union.is_incomplete(&env.library) {
+        Vec::new()
+    } else {
+        fields::analyze(env, &union.fields, obj, false, &functions, &mut imports)
+    };
+    let tag_field = analyze_tag_field(env, obj, &fields);
+
+    let base = InfoBase {
+        full_name,
+        type_id: union_tid,
+        name,
+        functions,
+        specials,
+        imports,
+        version: obj.version,
+        deprecated_version: None,
+        cfg_condition: obj.cfg_condition.clone(),
+        concurrency: obj.concurrency,
+        visibility: obj.visibility,
+    };
+
+    let info = Info {
+        base,
+        glib_get_type,
+        derives,
+        boxed_inline,
+        init_function_expression: obj.init_function_expression.clone(),
+        copy_into_function_expression: obj.copy_into_function_expression.clone(),
+        clear_function_expression: obj.clear_function_expression.clone(),
+        fields,
+        tag_field,
+    };
+
+    Some(info)
+}
+
+fn analyze_tag_field(env: &Env, obj: &GObject, fields: &[fields::Info]) -> Option<String> {
+    let tag_field = mangle_keywords(obj.tag_field.as_deref()?, env.config.edition).into_owned();
+    if !fields.iter().any(|f| f.name == tag_field) {
+        error!(
+            "Tag field `{}` of {} is missing or can't be read",
+            tag_field, obj.name
+        );
+    }
+    Some(tag_field)
+}
@@ -0,0 +1,388 @@
+use super::{
+    bounds::Bounds,
+    conversion_type::ConversionType,
+    ffi_type::used_ffi_type,
+    imports::Imports,
+    rust_type::RustType,
+    trampoline_parameters,
+    trampolines::{self, Trampoline},
+};
+use crate::{
+    config::{
+        self,
+        gobjects::{GObject, GStatus},
+        matchable::Matchable,
+    },
+    env::Env,
+    library,
+    nameutil::{self, use_glib_type},
+    traits::MaybeRefAs,
+    version::Version,
+};
+
+/// A virtual method that gets an overridable method in the generated
+/// `<Type>Impl` trait, a `parent_*` chain-up in `<Type>ImplExt` and a
+/// trampoline installed in the class (or interface) structure.
+#[derive(Debug)]
+pub struct Info {
+    /// Name of the method in the `Impl` trait.
+    pub name: String,
+    /// Name of the field in the C class or interface structure.
+    pub vfunc_name: String,
+    pub status: GStatus,
+    pub trampoline: Result<Trampoline, Vec<String>>,
+    pub version: Option<Version>,
+    pub deprecated_version: Option<Version>,
+    pub cfg_condition: Option<String>,
+    pub doc_hidden: bool,
+    pub unsafe_: bool,
+    /// The signal this virtual method is the class handler of, with the
+    /// emission stage it runs in.
+    pub class_handler_of: Option<(String, library::SignalEmissionStage)>,
+}
+
+impl Info {
+    pub fn parent_name(&self) -> String {
+        format!("parent_{}", self.name)
+    }
+}
+
+/// Everything needed to generate the `subclass/<type>.rs` module of a class
+/// or an interface.
+#[derive(Debug)]
+pub struct Subclass {
+    pub impl_trait_name: String,
+    /// The C class (or interface) structure holding the virtual methods.
+    pub c_class_type: String,
+    /// The `Impl` trait of the parent class (or of the class prerequisite
+    /// for interfaces) the generated `Impl` trait extends.
+    pub parent_impl_trait_name: String,
+    pub virtual_methods: Vec<Info>,
+    pub imports: Imports,
+}
+
+impl Subclass {
+    pub fn impl_ext_trait_name(&self) -> String {
+        format!("{}Ext", self.impl_trait_name)
+    }
+}
+
+pub fn analyze_subclass(
+    env: &Env,
+    virtual_methods: &[library::Function],
+    signals: &[library::Signal],
+    type_tid: library::TypeId,
+    parent_tid: Option<library::TypeId>,
+    c_class_type: &str,
+    class_fields: &[library::Field],
+    obj: &GObject,
+) -> Subclass {
+    let name = env.library.type_(type_tid).get_name();
+    let mut imports = Imports::new(&env.library);
+    imports.add("crate::ffi");
+    imports.add(&format!("crate::{name}"));
+    imports.add(&use_glib_type(env, "prelude::*"));
+    imports.add(&use_glib_type(env, "subclass::prelude::*"));
+    imports.add(&use_glib_type(env, "translate::*"));
+
+    let parent_impl_trait_name = match parent_tid {
+        Some(parent_tid) if parent_tid.full_name(&env.library) != "GObject.Object" => {
+            let crate_name = if parent_tid.ns_id == library::MAIN_NAMESPACE {
+                "crate"
+            } else {
+                &env.namespaces[parent_tid.ns_id].higher_crate_name
+            };
+            imports.add(&format!("{crate_name}::subclass::prelude::*"));
+            format!("{}Impl", env.library.type_(parent_tid).get_name())
+        }
+        _ => "ObjectImpl".to_owned(),
+    };
+
+    let virtual_methods = analyze(
+        env,
+        virtual_methods,
+        signals,
+        type_tid,
+        c_class_type,
+        class_fields,
+        obj,
+        &mut imports,
+    );
+
+    Subclass {
+        impl_trait_name: format!("{name}Impl"),
+        c_class_type: c_class_type.to_owned(),
+        parent_impl_trait_name,
+        virtual_methods,
+        imports,
+    }
+}
+
+fn analyze(
+    env: &Env,
+    virtual_methods: &[library::Function],
+    signals: &[library::Signal],
+    type_tid: library::TypeId,
+    c_class_type: &str,
+    class_fields: &[library::Field],
+    obj: &GObject,
+    imports: &mut Imports,
+) -> Vec<Info> {
+    let mut vfuncs = Vec::with_capacity(virtual_methods.len());
+
+    for func in virtual_methods {
+        let configured_vfuncs = obj.virtual_methods.matched(&func.name);
+        if !func.introspectable || configured_vfuncs.iter().any(|f| f.status.ignored()) {
+            continue;
+        }
+        if env.is_totally_deprecated(Some(type_tid.ns_id), func.deprecated_version) {
+            continue;
+        }
+
+        vfuncs.push(analyze_virtual_method(
+            env,
+            func,
+            signals,
+            type_tid,
+            c_class_type,
+            class_fields,
+            &configured_vfuncs,
+            obj,
+            imports,
+        ));
+    }
+
+    vfuncs
+}
+
+fn analyze_virtual_method(
+    env: &Env,
+    func: &library::Function,
+    signals: &[library::Signal],
+    type_tid: library::TypeId,
+    c_class_type: &str,
+    class_fields: &[library::Field],
+    configured_vfuncs: &[&config::virtual_methods::VirtualMethod],
+    obj: &GObject,
+    imports: &mut Imports,
+) -> Info {
+    let status = if configured_vfuncs.iter().any(|f| f.status.manual()) {
+        GStatus::Manual
+    } else {
+        GStatus::Generate
+    };
+    let version = configured_vfuncs
+        .iter()
+        .filter_map(|f| f.version)
+        .min()
+        .or(func.version);
+    let version = env.config.filter_version(version);
+    let cfg_condition = configured_vfuncs
+        .iter()
+        .find_map(|f| f.cfg_condition.clone());
+    let doc_hidden = configured_vfuncs.iter().any(|f| f.doc_hidden);
+    let unsafe_ = configured_vfuncs.iter().any(|f| f.unsafe_);
+
+    let name = configured_vfuncs
+        .iter()
+        .find_map(|f| f.rename.clone())
+        .or_else(|| {
+            if configured_vfuncs.iter().any(|f| f.bypass_auto_rename) {
+                return None;
+            }
+            let is_bool_getter = func
+                .parameters
+                .iter()
+                .all(|par| par.direction == library::ParameterDirection::In)
+                && (func.ret.typ == library::TypeId::tid_bool()
+                    || func.ret.typ == library::TypeId::tid_c_bool());
+            getter_rules::try_rename_would_be_getter(&func.name, is_bool_getter)
+                .ok()
+                .map(getter_rules::NewName::unwrap)
+        })
+        .unwrap_or_else(|| func.name.clone());
+    let name = nameutil::mangle_keywords(name, env.config.edition).into_owned();
+
+    // The trampoline is installed in the field of the class structure, which
+    // may be named or typed differently than the `<virtual-method>`
+    let field = class_fields.iter().find(|field| field.name == func.name);
+    let vfunc_name = field.map_or_else(|| func.name.clone(), |field| field.name.clone());
+
+    // The class closure of a signal calls the field at its offset in the class
+    // structure, signals without an emission stage have no class closure
+    let class_handler_of = signals
+        .iter()
+        .find(|signal| nameutil::signal_to_snake(&signal.name) == vfunc_name)
+        .and_then(|signal| Some((signal.name.clone(), signal.when?)));
+    let signature = field.and_then(|field| {
+        env.library
+            .type_(field.typ)
+            .maybe_ref_as::<library::Function>()
+    });
+
+    let mut used_types = Vec::with_capacity(4);
+    let trampoline = match signature {
+        Some(signature) => analyze_trampoline(
+            env,
+            &func.name,
+            signature,
+            type_tid,
+            configured_vfuncs,
+            obj,
+            &mut used_types,
+        ),
+        None => {
+            warn_main!(
+                type_tid,
+                "No field for virtual method '{}' in {}",
+                func.name,
+                c_class_type
+            );
+            Err(vec![format!(
+                "No callback field `{}` in {}",
+                func.name, c_class_type
+            )])
+        }
+    };
+
+    if status.need_generate() && trampoline.is_ok() {
+        let imports = &mut imports.with_defaults(version, &cfg_condition);
+        imports.add_used_types(&used_types);
+    }
+
+    Info {
+        name,
+        vfunc_name,
+        status,
+        trampoline,
+        version,
+        deprecated_version: func.deprecated_version,
+        cfg_condition,
+        doc_hidden,
+        unsafe_,
+        class_handler_of,
+    }
+}
+
+/// Analyzes the trampoline from the signature of the class structure field,
+/// whose first parameter is the instance.
+fn analyze_trampoline(
+    env: &Env,
+    vfunc_name: &str,
+    func: &library::Function,
+    type_tid: library::TypeId,
+    configured_vfuncs: &[&config::virtual_methods::VirtualMethod],
+    obj: &GObject,
+    used_types: &mut Vec<String>,
+) -> Result<Trampoline, Vec<String>> {
+    let Some(instance) = func.parameters.first() else {
+        return Err(vec![format!(
+            "Virtual method '{vfunc_name}' has no instance parameter"
+        )]);
+    };
+    let parameters = &func.parameters[1..];
+
+    let mut errors = Vec::new();
+    for par in parameters {
+        if let Some(error) = trampolines::type_error(env, par) {
+            errors.push(format!(
+                "{} {}: {}",
+                error,
+                par.name,
+                par.typ.full_name(&env.library)
+            ));
+        }
+    }
+    if func.ret.typ != Default::default() {
+        if let Some(error) = trampolines::type_error(env, &func.ret) {
+            errors.push(format!(
+                "{} return value {}",
+                error,
+                func.ret.typ.full_name(&env.library)
+            ));
+        } else if func.ret.transfer != library::Transfer::Full
+            && ConversionType::of(env, func.ret.typ) == ConversionType::Pointer
+        {
+            // Nothing would keep the returned value alive once the Rust
+            // implementation returns.
+            errors.push(format!(
+                "Transfer none return value {}",
+                func.ret.typ.full_name(&env.library)
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        warn_main!(
+            type_tid,
+            "Can't generate {} trampoline for virtual method '{}'",
+            type_tid.full_name(&env.library),
+            vfunc_name
+        );
+        return Err(errors);
+    }
+
+    let mut parameters = trampoline_parameters::analyze(env, parameters, type_tid, &[], None);
+    // Keep the constness of the instance parameter the vfunc was declared with
+    parameters.c_parameters[0].c_type = instance.c_type.clone();
+    for rust_par in parameters.rust_parameters.iter_mut().skip(1) {
+        if let Some(nullable) = configured_vfuncs
+            .iter()
+            .flat_map(|f| f.parameters.matched(&rust_par.name))
+            .find_map(|p| p.nullable)
+        {
+            rust_par.nullable = nullable;
+        }
+    }
+
+    for par in &parameters.rust_parameters {
+        if let Ok(rust_type) = RustType::builder(env, par.typ)
+            .direction(par.direction)
+            .try_from_glib(&par.try_from_glib)
+            .try_build()
+        {
+            used_types.extend(rust_type.into_used_types());
+        }
+    }
+    for par in &parameters.c_parameters {
+        if let Some(ffi_type) = used_ffi_type(env, par.typ, &par.c_type) {
+            used_types.push(ffi_type);
+        }
+    }
+
+    let mut ret = func.ret.clone();
+    if ret.typ != Default::default() {
+        if let Ok(rust_type) = RustType::builder(env, ret.typ)
+            .direction(library::ParameterDirection::Out)
+            .try_build()
+        {
+            used_types.extend(rust_type.into_used_types());
+        }
+        if let Some(ffi_type) = used_ffi_type(env, ret.typ, &ret.c_type) {
+            used_types.push(ffi_type);
+        }
+        if let Some(nullable) = configured_vfuncs.iter().find_map(|f| f.ret.nullable) {
+            ret.nullable = nullable;
+        }
+    }
+
+    Ok(Trampoline {
+        name: format!(
+            "{}_{}",
+            nameutil::module_name(&env.library.type_(type_tid).get_name(), env.config.edition),
+            vfunc_name
+        ),
+        parameters,
+        ret,
+        bound_name: String::new(),
+        bounds: Bounds::default(),
+        version: None,
+        inhibit: false,
+        concurrency: obj.concurrency,
+        is_notify: false,
+        scope: library::ParameterScope::None,
+        user_data_index: 0,
+        destroy_index: 0,
+        nullable: library::Nullable(false),
+        type_name: env.library.type_(type_tid).get_name(),
+    })
+}
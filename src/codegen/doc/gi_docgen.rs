@@ -210,7 +210,7 @@ fn find_virtual_method_by_name(
         env,
         in_type,
         |f| {
-            f.name == mangle_keywords(name)
+            f.name == mangle_keywords(name, env.config.edition)
                 && namespace.as_ref().map_or(f.ns_id == MAIN_NAMESPACE, |n| {
                     &env.library.namespaces[f.ns_id as usize].name == n
                 })
@@ -240,7 +240,7 @@ fn find_method_or_function_by_name(
         env,
         in_type,
         |f| {
-            f.name == mangle_keywords(name)
+            f.name == mangle_keywords(name, env.config.edition)
                 && namespace.as_ref().map_or(f.ns_id == MAIN_NAMESPACE, |n| {
                     &env.library.namespaces[f.ns_id as usize].name == n
                 })
@@ -386,7 +386,7 @@ impl GiDocgen {
                 .find_object_by_function(
                     env,
                     |o| &o.name == type_ && is_same_namespace(env, namespace.as_deref(), o.type_id),
-                    |f| f.name == mangle_keywords(name),
+                    |f| f.name == mangle_keywords(name, env.config.edition),
                 )
                 .map_or_else(
                     || format!("`{}::{}()`", ns_type_to_doc(namespace, type_), name),
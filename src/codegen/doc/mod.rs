@@ -12,7 +12,7 @@ use stripper_lib::{write_file_name, write_item_doc, Type as SType, TypeStruct};
 use self::format::reformat_doc;
 use crate::{
     analysis::{self, namespaces::MAIN, object::LocationInObject},
-    config::gobjects::GObject,
+    config::{gobjects::GObject, Edition},
     env::Env,
     file_saver::save_to_file,
     library::{self, Type as LType, *},
@@ -35,18 +35,18 @@ const IGNORED_C_FN_PARAMS: [&str; 6] = [
 ];
 
 trait ToStripperType {
-    fn to_stripper_type(&self) -> TypeStruct;
+    fn to_stripper_type(&self, edition: Edition) -> TypeStruct;
 }
 
 macro_rules! impl_to_stripper_type {
     ($ty:ident, $enum_var:ident, $useless:expr) => {
         impl ToStripperType for $ty {
-            fn to_stripper_type(&self) -> TypeStruct {
+            fn to_stripper_type(&self, edition: Edition) -> TypeStruct {
                 TypeStruct::new(
                     SType::$enum_var,
                     &format!(
                         "connect_{}",
-                        nameutil::mangle_keywords(nameutil::signal_to_snake(&self.name))
+                        nameutil::mangle_keywords(nameutil::signal_to_snake(&self.name), edition)
                     ),
                 )
             }
@@ -54,8 +54,11 @@ macro_rules! impl_to_stripper_type {
     };
     ($ty:ident, $enum_var:ident) => {
         impl ToStripperType for $ty {
-            fn to_stripper_type(&self) -> TypeStruct {
-                TypeStruct::new(SType::$enum_var, &nameutil::mangle_keywords(&self.name))
+            fn to_stripper_type(&self, edition: Edition) -> TypeStruct {
+                TypeStruct::new(
+                    SType::$enum_var,
+                    &nameutil::mangle_keywords(&self.name, edition),
+                )
             }
         }
     };
@@ -94,6 +97,7 @@ macro_rules! impl_function_like_type {
 impl_to_stripper_type!(Enumeration, Enum);
 impl_to_stripper_type!(Bitfield, Struct);
 impl_to_stripper_type!(Record, Struct);
+impl_to_stripper_type!(Union, Struct);
 impl_to_stripper_type!(Class, Struct);
 impl_to_stripper_type!(Function, Fn);
 impl_to_stripper_type!(Signal, Fn, false);
@@ -103,9 +107,7 @@ impl_function_like_type!(Signal);
 
 pub fn generate(env: &Env) {
     info!("Generating documentation {:?}", env.config.doc_target_path);
-    save_to_file(&env.config.doc_target_path, env.config.make_backup, |w| {
-        generate_doc(w, env)
-    });
+    save_to_file(env, &env.config.doc_target_path, |w| generate_doc(w, env));
 }
 
 #[allow(clippy::type_complexity)]
@@ -131,6 +133,15 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
         }
     }
 
+    for info in env.analysis.unions.values() {
+        if info.type_id.ns_id == MAIN {
+            generators.push((
+                &info.name,
+                Box::new(move |w, e| create_union_doc(w, e, info)),
+            ));
+        }
+    }
+
     for (tid, type_) in env.library.namespace_types(MAIN) {
         if let LType::Enumeration(enum_) = type_ {
             if !env
@@ -209,6 +220,7 @@ fn generate_doc(w: &mut dyn Write, env: &Env) -> Result<()> {
                     &doc_ignored_parameters,
                     None,
                     f_info.map_or(true, |f| f.generate_doc),
+                    None,
                 )?;
             }
         }
@@ -419,7 +431,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                         w,
                         "{}",
                         reformat_doc(
-                            &fix_param_names(doc, &None),
+                            &fix_param_names(doc, &None, env.config.edition),
                             env,
                             Some((&info.type_id, Some(LocationInObject::Builder)))
                         )
@@ -430,12 +442,15 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                         w,
                         "{}",
                         reformat_doc(
-                            &fix_param_names(doc, &None),
+                            &fix_param_names(doc, &None, env.config.edition),
                             env,
                             Some((&info.type_id, Some(LocationInObject::Builder)))
                         )
                     )?;
                 }
+                if let Some(ref default_value) = property.default_value {
+                    writeln!(w, "\nDefaults to `{default_value}`.")?;
+                }
                 Ok(())
             })?;
         }
@@ -531,6 +546,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                 &doc_ignored_parameters,
                 Some((&info.type_id, object_location)),
                 f_info.map_or(true, |f| f.generate_doc),
+                None,
             )?;
         }
     }
@@ -557,6 +573,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
             &HashSet::new(),
             Some((&info.type_id, object_location)),
             configured_signals.iter().all(|s| s.generate_doc),
+            signal_emission_doc(signal),
         )?;
     }
 
@@ -605,6 +622,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
                 &doc_ignored_parameters,
                 Some((&info.type_id, object_location)),
                 f_info.map_or(true, |f| f.generate_doc),
+                class_handler_doc(signals, function),
             )?;
         }
     }
@@ -658,7 +676,7 @@ fn create_object_doc(w: &mut dyn Write, env: &Env, info: &analysis::object::Info
 
 fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info) -> Result<()> {
     let record: &Record = env.library.type_(info.type_id).to_ref_as();
-    let ty = record.to_stripper_type();
+    let ty = record.to_stripper_type(env.config.edition);
     let object = env.config.objects.get(&info.full_name);
     let trait_name = object
         .and_then(|o| o.trait_name.clone())
@@ -709,6 +727,55 @@ fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info
                 &HashSet::new(),
                 Some((&info.type_id, None)),
                 f_info.map_or(true, |f| f.generate_doc),
+                None,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn create_union_doc(w: &mut dyn Write, env: &Env, info: &analysis::union::Info) -> Result<()> {
+    let union: &Union = env.library.type_(info.type_id).to_ref_as();
+    let ty = union.to_stripper_type(env.config.edition);
+    let generate_doc = env
+        .config
+        .objects
+        .get(&info.full_name)
+        .is_none_or(|r| r.generate_doc);
+    if generate_doc {
+        write_item_doc(w, &ty, |w| {
+            if let Some(ref doc) = union.doc {
+                writeln!(w, "{}", reformat_doc(doc, env, Some((&info.type_id, None))))?;
+            }
+            Ok(())
+        })?;
+    }
+
+    for function in &union.functions {
+        let function_ty = TypeStruct {
+            ty: SType::Impl,
+            parent: ty.parent.clone(),
+            name: ty.name.clone(),
+            args: ty.args.clone(),
+        };
+        if let Some(c_identifier) = &function.c_identifier {
+            let f_info = info.functions.iter().find(|f| &f.glib_name == c_identifier);
+            let should_be_documented = f_info.is_some_and(|f| f.should_docs_be_generated(env));
+            if !should_be_documented {
+                continue;
+            }
+            let fn_new_name = f_info.and_then(|analysed_f| analysed_f.new_name.clone());
+
+            create_fn_doc(
+                w,
+                env,
+                function,
+                Some(Box::new(function_ty)),
+                fn_new_name,
+                &HashSet::new(),
+                Some((&info.type_id, None)),
+                f_info.is_none_or(|f| f.generate_doc),
+                None,
             )?;
         }
     }
@@ -716,7 +783,7 @@ fn create_record_doc(w: &mut dyn Write, env: &Env, info: &analysis::record::Info
 }
 
 fn create_enum_doc(w: &mut dyn Write, env: &Env, enum_: &Enumeration, tid: TypeId) -> Result<()> {
-    let ty = enum_.to_stripper_type();
+    let ty = enum_.to_stripper_type(env.config.edition);
     let config = env.config.objects.get(&tid.full_name(&env.library));
 
     if config.map_or(true, |c| c.generate_doc) {
@@ -774,7 +841,7 @@ fn create_bitfield_doc(
     bitfield: &Bitfield,
     tid: TypeId,
 ) -> Result<()> {
-    let ty = bitfield.to_stripper_type();
+    let ty = bitfield.to_stripper_type(env.config.edition);
     let config = env.config.objects.get(&tid.full_name(&env.library));
 
     write_item_doc(w, &ty, |w| {
@@ -831,14 +898,14 @@ fn param_name() -> &'static Regex {
     REGEX.get_or_init(|| Regex::new(r"@(\w+)\b").unwrap())
 }
 
-fn fix_param_names<'a>(doc: &'a str, self_name: &Option<String>) -> Cow<'a, str> {
+fn fix_param_names<'a>(doc: &'a str, self_name: &Option<String>, edition: Edition) -> Cow<'a, str> {
     param_name().replace_all(doc, |caps: &Captures<'_>| {
         if let Some(self_name) = self_name {
             if &caps[1] == self_name {
                 return "@self".into();
             }
         }
-        format!("@{}", nameutil::mangle_keywords(&caps[1]))
+        format!("@{}", nameutil::mangle_keywords(&caps[1], edition))
     })
 }
 
@@ -851,6 +918,7 @@ fn create_fn_doc<T>(
     doc_ignored_parameters: &HashSet<String>,
     in_type: Option<(&TypeId, Option<LocationInObject>)>,
     generate_doc: bool,
+    notes: Option<String>,
 ) -> Result<()>
 where
     T: FunctionLikeType + ToStripperType,
@@ -869,9 +937,9 @@ where
         return Ok(());
     }
 
-    let mut st = fn_.to_stripper_type();
+    let mut st = fn_.to_stripper_type(env.config.edition);
     if let Some(name_override) = name_override {
-        st.name = nameutil::mangle_keywords(name_override).to_string();
+        st.name = nameutil::mangle_keywords(name_override, env.config.edition).to_string();
     }
     let ty = TypeStruct { parent, ..st };
     let self_name: Option<String> = fn_
@@ -885,7 +953,11 @@ where
             writeln!(
                 w,
                 "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
+                reformat_doc(
+                    &fix_param_names(doc, &self_name, env.config.edition),
+                    env,
+                    in_type
+                )
             )?;
         }
         if let Some(ver) = fn_.deprecated_version() {
@@ -897,9 +969,16 @@ where
             writeln!(
                 w,
                 "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
+                reformat_doc(
+                    &fix_param_names(doc, &self_name, env.config.edition),
+                    env,
+                    in_type
+                )
             )?;
         }
+        if let Some(notes) = &notes {
+            writeln!(w, "\n{notes}\n")?;
+        }
 
         // A list of parameter positions to filter out
         let mut indices_to_ignore: BTreeSet<_> = fn_
@@ -941,12 +1020,16 @@ where
                 writeln!(
                     w,
                     "## `{}`",
-                    nameutil::mangle_keywords(parameter.name.as_str())
+                    nameutil::mangle_keywords(parameter.name.as_str(), env.config.edition)
                 )?;
                 writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
+                    reformat_doc(
+                        &fix_param_names(doc, &self_name, env.config.edition),
+                        env,
+                        in_type
+                    )
                 )?;
             }
         }
@@ -968,7 +1051,11 @@ where
             writeln!(
                 w,
                 "{}",
-                reformat_doc(&fix_param_names(doc, &self_name), env, in_type)
+                reformat_doc(
+                    &fix_param_names(doc, &self_name, env.config.edition),
+                    env,
+                    in_type
+                )
             )?;
         }
         // document OUT parameters as part of the function's Return
@@ -977,12 +1064,16 @@ where
                 writeln!(
                     w,
                     "\n## `{}`",
-                    nameutil::mangle_keywords(parameter.name.as_str())
+                    nameutil::mangle_keywords(parameter.name.as_str(), env.config.edition)
                 )?;
                 writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &self_name), env, in_type),
+                    reformat_doc(
+                        &fix_param_names(doc, &self_name, env.config.edition),
+                        env,
+                        in_type
+                    ),
                 )?;
             }
         }
@@ -990,6 +1081,50 @@ where
     })
 }
 
+/// Explains when the handlers connected with `connect_*` run relative to the
+/// class handler of the signal.
+fn signal_emission_doc(signal: &Signal) -> Option<String> {
+    let mut notes = Vec::new();
+    match signal.when {
+        Some(SignalEmissionStage::First) => notes.push(
+            "The class handler of this signal runs before the handlers connected with this function.",
+        ),
+        Some(SignalEmissionStage::Last) => notes.push(
+            "The class handler of this signal runs after the handlers connected with this function, \
+            connect with `ObjectExt::connect()` and `after` set to `true` to run after it.",
+        ),
+        Some(SignalEmissionStage::Cleanup) => {
+            notes.push("The class handler of this signal runs after all the connected handlers.")
+        }
+        None => {}
+    }
+    if signal.no_recurse {
+        notes.push(
+            "Emitting this signal from one of its handlers restarts the emission instead of \
+            recursing into it.",
+        );
+    }
+    (!notes.is_empty()).then(|| notes.join(" "))
+}
+
+/// Documents the virtual methods used as the class handler of a signal.
+fn class_handler_doc(signals: &[Signal], vfunc: &Function) -> Option<String> {
+    let signal = signals
+        .iter()
+        .find(|signal| nameutil::signal_to_snake(&signal.name) == vfunc.name)?;
+    let stage = match signal.when? {
+        SignalEmissionStage::First => "before the handlers connected to it",
+        SignalEmissionStage::Last => {
+            "after the handlers connected to it, unless they were connected with `after` set to `true`"
+        }
+        SignalEmissionStage::Cleanup => "after all the handlers connected to it",
+    };
+    Some(format!(
+        "This is the class handler of the `{}` signal, it runs {stage}.",
+        signal.name
+    ))
+}
+
 fn create_property_doc(
     w: &mut dyn Write,
     env: &Env,
@@ -1038,7 +1173,11 @@ fn create_property_doc(
                 writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &None), env, Some(in_type))
+                    reformat_doc(
+                        &fix_param_names(doc, &None, env.config.edition),
+                        env,
+                        Some(in_type)
+                    )
                 )?;
             }
             if let Some(ver) = property.deprecated_version {
@@ -1050,7 +1189,11 @@ fn create_property_doc(
                 writeln!(
                     w,
                     "{}",
-                    reformat_doc(&fix_param_names(doc, &None), env, Some(in_type))
+                    reformat_doc(
+                        &fix_param_names(doc, &None, env.config.edition),
+                        env,
+                        Some(in_type)
+                    )
                 )?;
             }
             Ok(())
@@ -1129,13 +1272,18 @@ pub fn document_type_properties(
         if property.construct_only {
             details.push("Construct Only");
         }
+        let default_value = property
+            .default_value
+            .as_ref()
+            .map(|default_value| format!("Default value: `{default_value}`"));
+        details.extend(default_value.as_deref());
         if let Some(doc) = &property.doc {
             writeln!(
                 w,
                 "\n\n#### `{}`\n {}\n\n{}",
                 property.name,
                 reformat_doc(
-                    &fix_param_names(doc, &None),
+                    &fix_param_names(doc, &None, env.config.edition),
                     env,
                     Some((&info.type_id, None))
                 ),
@@ -1169,13 +1317,25 @@ pub fn document_type_signals(
         if signal.is_detailed {
             details.push("Detailed");
         }
+        match signal.when {
+            Some(SignalEmissionStage::First) => details.push("Run First"),
+            Some(SignalEmissionStage::Last) => details.push("Run Last"),
+            Some(SignalEmissionStage::Cleanup) => details.push("Run Cleanup"),
+            None => {}
+        }
+        if signal.no_recurse {
+            details.push("No Recurse");
+        }
+        if signal.no_hooks {
+            details.push("No Hooks");
+        }
         if let Some(doc) = &signal.doc {
             writeln!(
                 w,
                 "\n\n#### `{}`\n {}\n\n{}",
                 signal.name,
                 reformat_doc(
-                    &fix_param_names(doc, &None),
+                    &fix_param_names(doc, &None, env.config.edition),
                     env,
                     Some((&info.type_id, None))
                 ),
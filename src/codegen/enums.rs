@@ -11,8 +11,8 @@ use crate::{
     codegen::{
         general::{
             self, allow_deprecated, cfg_condition, cfg_condition_no_doc, cfg_condition_string,
-            cfg_deprecated, derives, doc_alias, version_condition, version_condition_no_doc,
-            version_condition_string,
+            cfg_deprecated, derives, doc_alias, unsafe_in_unsafe_fn, version_condition,
+            version_condition_no_doc, version_condition_string,
         },
         generate_default_impl,
     },
@@ -36,7 +36,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
     }
 
     let path = root_path.join("enums.rs");
-    file_saver::save_to_file(path, env.config.make_backup, |w| {
+    file_saver::save_to_file(env, path, |w| {
         general::start_comments(w, &env.config)?;
         general::uses(w, env, &env.analysis.enum_imports, None)?;
         writeln!(w)?;
@@ -316,7 +316,11 @@ impl FromGlib<{sys_crate_name}::{ffi_name}> for {_enum_name} {{
             .collect::<Vec<_>>()
             .join(", ");
         writeln!(w, "debug_assert!([{all_members}].contains(&value));")?;
-        writeln!(w, "std::mem::transmute(value)",)?;
+        writeln!(
+            w,
+            "{}",
+            unsafe_in_unsafe_fn(env, "std::mem::transmute(value)")
+        )?;
     } else {
         writeln!(w, "match value {{")?;
         for member in &members {
@@ -481,10 +485,16 @@ impl FromGlib<{sys_crate_name}::{ffi_name}> for {_enum_name} {{
 
     #[inline]
     unsafe fn from_value(value: &'a {gvalue}) -> Self {{
-        {assert}from_glib({glib}(value.to_glib_none().0))
+        {assert}{from_value}
     }}
 }}",
-            glib = use_glib_type(env, "gobject_ffi::g_value_get_enum"),
+            from_value = unsafe_in_unsafe_fn(
+                env,
+                &format!(
+                    "from_glib({}(value.to_glib_none().0))",
+                    use_glib_type(env, "gobject_ffi::g_value_get_enum")
+                )
+            ),
             gvalue = use_glib_type(env, "Value"),
             genericwrongvaluetypechecker = use_glib_type(env, "value::GenericValueTypeChecker"),
             assert = assert,
@@ -10,8 +10,8 @@ use crate::{
     codegen::{
         general::{
             self, cfg_condition, cfg_condition_doc, cfg_condition_no_doc, cfg_condition_string,
-            cfg_deprecated, derives, doc_alias, version_condition, version_condition_doc,
-            version_condition_no_doc, version_condition_string,
+            cfg_deprecated, derives, doc_alias, unsafe_in_unsafe_fn, version_condition,
+            version_condition_doc, version_condition_no_doc, version_condition_string,
         },
         generate_default_impl,
     },
@@ -34,7 +34,7 @@ pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
     }
 
     let path = root_path.join("flags.rs");
-    file_saver::save_to_file(path, env.config.make_backup, |w| {
+    file_saver::save_to_file(env, path, |w| {
         general::start_comments(w, &env.config)?;
         general::uses(w, env, &env.analysis.flags_imports, None)?;
         writeln!(w)?;
@@ -321,11 +321,17 @@ impl FromGlib<{sys_crate_name}::{ffi_name}> for {name} {{
 
     #[inline]
     unsafe fn from_value(value: &'a {gvalue}) -> Self {{
-        {assert}from_glib({glib}(value.to_glib_none().0))
+        {assert}{from_value}
     }}
 }}",
+            from_value = unsafe_in_unsafe_fn(
+                env,
+                &format!(
+                    "from_glib({}(value.to_glib_none().0))",
+                    use_glib_type(env, "gobject_ffi::g_value_get_flags")
+                )
+            ),
             name = flag_name(&flags.name),
-            glib = use_glib_type(env, "gobject_ffi::g_value_get_flags"),
             gvalue = use_glib_type(env, "Value"),
             genericwrongvaluetypechecker = use_glib_type(env, "value::GenericValueTypeChecker"),
             assert = assert,
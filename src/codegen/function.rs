@@ -10,7 +10,7 @@ use super::{
     function_body_chunk,
     general::{
         allow_deprecated, cfg_condition, cfg_deprecated, doc_alias, doc_hidden,
-        not_version_condition, version_condition,
+        not_version_condition, unsafe_in_unsafe_fn, version_condition,
     },
     parameter::ToParameter,
     return_value::{out_parameter_types, out_parameters_as_return, ToReturnValue},
@@ -31,6 +31,7 @@ use crate::{
 // If `Self` is returned (so `-> Self`) in a method (whatever the form of the
 // `self`), then the `#[must_use]` attribute must be added.
 pub fn get_must_use_if_needed(
+    env: &Env,
     parent_type_id: Option<TypeId>,
     analysis: &analysis::functions::Info,
     comment_prefix: &str,
@@ -42,7 +43,7 @@ pub fn get_must_use_if_needed(
         // implementation).
         if analysis.kind == library::FunctionKind::Method {
             // We now get the list of the returned types.
-            let outs = out_parameter_types(analysis);
+            let outs = out_parameter_types(env, analysis);
             // If there is only one type returned, we check if it's the same type as `self`
             // (stored in `parent_type_id`).
             if [parent_type_id] == *outs.as_slice() {
@@ -95,6 +96,9 @@ pub fn generate(
     let suffix = if only_declaration { ";" } else { " {" };
 
     writeln!(w)?;
+    for reason in &analysis.skip_reasons {
+        writeln!(w, "{}// Unsupported: {}", tabs(indent), reason)?;
+    }
     cfg_deprecated(w, env, None, analysis.deprecated_version, commented, indent)?;
     cfg_condition(w, analysis.cfg_condition.as_ref(), commented, indent)?;
     let version = Version::if_stricter_than(analysis.version, scope_version);
@@ -106,6 +110,11 @@ pub fn generate(
     if analysis.codegen_name() != analysis.func_name {
         doc_alias(w, &analysis.func_name, comment_prefix, indent)?;
     }
+    if let Some(variadic) = &analysis.variadic {
+        if variadic.key_values.is_none() {
+            doc_alias(w, &variadic.glib_name, comment_prefix, indent)?;
+        }
+    }
     // TODO Warn the user if both get_property and set_property are set as it is
     // an error on the gir data.
     if let Some(get_property) = &analysis.get_property {
@@ -139,7 +148,7 @@ pub fn generate(
         "{}{}{}{}{}{}{}{}{}",
         allow_should_implement_trait,
         dead_code_cfg,
-        get_must_use_if_needed(parent_type_id, analysis, comment_prefix).unwrap_or_default(),
+        get_must_use_if_needed(env, parent_type_id, analysis, comment_prefix).unwrap_or_default(),
         tabs(indent),
         comment_prefix,
         pub_prefix,
@@ -191,12 +200,72 @@ pub fn generate(
         }
     }
 
+    if analysis.callback_stream.is_some() {
+        let declaration = declaration_stream(env, analysis);
+        let suffix = if only_declaration { ";" } else { " {" };
+
+        writeln!(w)?;
+        cfg_deprecated(w, env, None, analysis.deprecated_version, commented, indent)?;
+        cfg_condition(w, analysis.cfg_condition.as_ref(), commented, indent)?;
+        version_condition(w, env, None, version, commented, indent)?;
+        not_version_condition(w, analysis.not_version, commented, indent)?;
+        doc_hidden(w, analysis.doc_hidden, comment_prefix, indent)?;
+        writeln!(
+            w,
+            "{}{}{}{}{}{}",
+            tabs(indent),
+            comment_prefix,
+            pub_prefix,
+            unsafe_,
+            declaration,
+            suffix
+        )?;
+
+        if !only_declaration {
+            for s in body_chunk_stream(env, analysis).lines() {
+                writeln!(w, "{}{}{}", tabs(indent + 1), comment_prefix, s)?;
+            }
+            writeln!(w, "{}{}}}", tabs(indent), comment_prefix)?;
+        }
+    }
+
+    if let Some(variadic) = &analysis.variadic {
+        if variadic.key_values.is_some() {
+            let declaration = declaration_key_values(env, analysis);
+            let suffix = if only_declaration { ";" } else { " {" };
+
+            writeln!(w)?;
+            cfg_deprecated(w, env, None, analysis.deprecated_version, commented, indent)?;
+            cfg_condition(w, analysis.cfg_condition.as_ref(), commented, indent)?;
+            version_condition(w, env, None, version, commented, indent)?;
+            not_version_condition(w, analysis.not_version, commented, indent)?;
+            doc_hidden(w, analysis.doc_hidden, comment_prefix, indent)?;
+            doc_alias(w, &variadic.glib_name, comment_prefix, indent)?;
+            writeln!(
+                w,
+                "{}{}{}{}{}{}",
+                tabs(indent),
+                comment_prefix,
+                pub_prefix,
+                unsafe_,
+                declaration,
+                suffix
+            )?;
+
+            if !only_declaration {
+                for s in body_chunk_key_values(env, analysis).lines() {
+                    writeln!(w, "{}{}{}", tabs(indent + 1), comment_prefix, s)?;
+                }
+                writeln!(w, "{}{}}}", tabs(indent), comment_prefix)?;
+            }
+        }
+    }
+
     Ok(())
 }
 
-pub fn declaration(env: &Env, analysis: &analysis::functions::Info) -> String {
-    let outs_as_return = !analysis.outs.is_empty();
-    let return_str = if outs_as_return {
+fn return_str(env: &Env, analysis: &analysis::functions::Info) -> String {
+    if !analysis.outs.is_empty() {
         out_parameters_as_return(env, analysis)
     } else if analysis.ret.bool_return_is_error.is_some() {
         format!(" -> Result<(), {}>", use_glib_type(env, "error::BoolError"))
@@ -212,7 +281,11 @@ pub fn declaration(env: &Env, analysis: &analysis::functions::Info) -> String {
         format!(" -> {return_type}")
     } else {
         String::new()
-    };
+    }
+}
+
+pub fn declaration(env: &Env, analysis: &analysis::functions::Info) -> String {
+    let return_str = return_str(env, analysis);
     let mut param_str = String::with_capacity(100);
 
     let (bounds, _) = bounds(&analysis.bounds, &[], false, false);
@@ -285,6 +358,71 @@ pub fn declaration_futures(env: &Env, analysis: &analysis::functions::Info) -> S
     )
 }
 
+pub fn declaration_stream(env: &Env, analysis: &analysis::functions::Info) -> String {
+    let stream = analysis.callback_stream.as_ref().unwrap();
+
+    let mut param_str = String::with_capacity(100);
+    for par in &analysis.parameters.rust_parameters {
+        let c_par = &analysis.parameters.c_parameters[par.ind_c];
+        if c_par.name == stream.callback {
+            continue;
+        }
+        if !param_str.is_empty() {
+            param_str.push_str(", ");
+        }
+        param_str.push_str(&c_par.to_parameter(env, &analysis.bounds, false));
+    }
+
+    let skipped_bounds = analysis
+        .bounds
+        .get_parameter_bound(&stream.callback)
+        .and_then(|bound| bound.type_parameter_reference())
+        .into_iter()
+        .collect::<Vec<_>>();
+    let (bounds, _) = bounds(&analysis.bounds, &skipped_bounds, false, false);
+
+    format!(
+        "fn {}{}({}) -> Pin<Box_<dyn futures_core::Stream<Item = {}> + 'static>>",
+        stream.name, bounds, param_str, stream.item_type,
+    )
+}
+
+/// Takes the key/value pairs of a variadic function as a slice, in place of
+/// the arrays of keys and values.
+pub fn declaration_key_values(env: &Env, analysis: &analysis::functions::Info) -> String {
+    let key_values = analysis
+        .variadic
+        .as_ref()
+        .and_then(|variadic| variadic.key_values.as_ref())
+        .unwrap();
+
+    let mut param_str = String::with_capacity(100);
+    for par in &analysis.parameters.rust_parameters {
+        if par.name == key_values.values {
+            continue;
+        }
+        if !param_str.is_empty() {
+            param_str.push_str(", ");
+        }
+        if par.name == key_values.keys {
+            param_str.push_str(&format!("{}: &[(&str, &dyn ToValue)]", key_values.pairs));
+        } else {
+            let c_par = &analysis.parameters.c_parameters[par.ind_c];
+            param_str.push_str(&c_par.to_parameter(env, &analysis.bounds, false));
+        }
+    }
+
+    let (bounds, _) = bounds(&analysis.bounds, &[], false, false);
+
+    format!(
+        "fn {}{}({}){}",
+        key_values.name,
+        bounds,
+        param_str,
+        return_str(env, analysis),
+    )
+}
+
 pub fn bounds(
     bounds: &Bounds,
     skip: &[char],
@@ -367,7 +505,8 @@ pub fn body_chunk(
         .assertion(analysis.assertion)
         .ret(&analysis.ret)
         .transformations(&analysis.parameters.transformations)
-        .in_unsafe(analysis.unsafe_)
+        // Edition 2024 requires an `unsafe` block in `unsafe fn` too
+        .in_unsafe(analysis.unsafe_ && !env.config.edition.is_2024())
         .outs_mode(analysis.outs.mode);
 
     if analysis.r#async {
@@ -401,6 +540,105 @@ pub fn body_chunk(
     builder.generate(env, &bounds, &bounds_names.join(", "))
 }
 
+/// Calls the function with a closure sending its arguments to the stream,
+/// which ends when the closure is dropped by the destroy notify.
+pub fn body_chunk_stream(env: &Env, analysis: &analysis::functions::Info) -> String {
+    let stream = analysis.callback_stream.as_ref().unwrap();
+    let is_method = analysis.kind == library::FunctionKind::Method;
+
+    let value = match stream.values.as_slice() {
+        [value] => value.clone(),
+        values => format!("({})", values.join(", ")),
+    };
+    let closure = format!(
+        "move |{}| {{\n\t\tlet _ = sender.unbounded_send({value});\n\t}}",
+        stream.parameters.join(", ")
+    );
+
+    let mut call = String::new();
+    if is_method {
+        call.push_str(&format!("self.{}(", analysis.codegen_name()));
+    } else if analysis.type_name.is_ok() {
+        call.push_str(&format!("Self::{}(", analysis.codegen_name()));
+    } else {
+        call.push_str(&format!("{}(", analysis.codegen_name()));
+    }
+    call.push('\n');
+    for par in analysis
+        .parameters
+        .rust_parameters
+        .iter()
+        .skip(usize::from(is_method))
+    {
+        if par.name != stream.callback {
+            call.push_str(&format!("\t{},\n", par.name));
+        } else if *stream.nullable {
+            call.push_str(&format!("\tSome(Box_::new({closure})),\n"));
+        } else {
+            call.push_str(&format!("\t{closure},\n"));
+        }
+    }
+    call.push_str(");\n");
+
+    let mut body = String::new();
+    body.push_str("let (sender, receiver) = futures_channel::mpsc::unbounded();\n");
+    // Edition 2024 requires an `unsafe` block in `unsafe fn` too
+    if analysis.unsafe_ && env.config.edition.is_2024() {
+        body.push_str("unsafe {\n");
+        for line in call.lines() {
+            body.push_str(&format!("\t{line}\n"));
+        }
+        body.push_str("}\n");
+    } else {
+        body.push_str(&call);
+    }
+    body.push_str("Box_::pin(receiver)");
+    body
+}
+
+/// Splits the key/value pairs into the arrays taken by the function.
+pub fn body_chunk_key_values(env: &Env, analysis: &analysis::functions::Info) -> String {
+    let key_values = analysis
+        .variadic
+        .as_ref()
+        .and_then(|variadic| variadic.key_values.as_ref())
+        .unwrap();
+    let is_method = analysis.kind == library::FunctionKind::Method;
+
+    let mut body = format!(
+        "let ({}, {}): (Vec<_>, Vec<_>) = {}\n\t.iter()\n\t.map(|(key, value)| (*key, value.to_value()))\n\t.unzip();\n",
+        key_values.keys, key_values.values, key_values.pairs
+    );
+    let mut call = if is_method {
+        format!("self.{}(", analysis.codegen_name())
+    } else if analysis.type_name.is_ok() {
+        format!("Self::{}(", analysis.codegen_name())
+    } else {
+        format!("{}(", analysis.codegen_name())
+    };
+    let args = analysis
+        .parameters
+        .rust_parameters
+        .iter()
+        .skip(usize::from(is_method))
+        .map(|par| {
+            if par.name == key_values.keys || par.name == key_values.values {
+                format!("&{}", par.name)
+            } else {
+                par.name.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    call.push_str(&args.join(", "));
+    call.push(')');
+    if analysis.unsafe_ {
+        body.push_str(&unsafe_in_unsafe_fn(env, &call));
+    } else {
+        body.push_str(&call);
+    }
+    body
+}
+
 pub fn body_chunk_futures(
     env: &Env,
     analysis: &analysis::functions::Info,
@@ -1,9 +1,10 @@
-use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 use crate::{
     analysis::{
         self,
         conversion_type::ConversionType,
+        ffi_type::ffi_type,
         function_parameters::{
             CParameter as AnalysisCParameter, Transformation, TransformationType,
         },
@@ -16,6 +17,7 @@ use crate::{
         trampolines::Trampoline,
     },
     chunk::{parameter_ffi_call_out, Chunk, Param, TupleMode},
+    codegen::translate_to_glib::hash_table_to_glib,
     env::Env,
     library::{self, ParameterDirection, TypeId},
     nameutil::{is_gstring, use_gio_type, use_glib_if_needed, use_glib_type},
@@ -69,12 +71,14 @@ pub struct Builder {
 }
 
 // Key: user data index
-// Value: (global position used as id, type, callbacks)
+// Value: (global position used as id, tuple type, callbacks)
 type FuncParameters<'a> = BTreeMap<usize, FuncParameter<'a>>;
 
 struct FuncParameter<'a> {
     pos: usize,
-    full_type: Option<(String, String)>,
+    /// Type of the tuple storing the closures when several of them share the
+    /// user data.
+    tuple_type: Option<String>,
     callbacks: Vec<&'a Trampoline>,
 }
 
@@ -163,40 +167,16 @@ impl Builder {
                     user_data_index,
                     FuncParameter {
                         pos,
-                        full_type: if calls.len() > 1 {
-                            if calls.iter().all(|c| c.scope.is_call()) {
-                                Some((
-                                    format!(
-                                        "&({})",
-                                        calls
-                                            .iter()
-                                            .map(|c| format!("&{}", c.bound_name))
-                                            .collect::<Vec<_>>()
-                                            .join(", ")
-                                    ),
-                                    format!(
-                                        "&mut ({})",
-                                        calls
-                                            .iter()
-                                            .map(|c| format!("&mut {}", c.bound_name))
-                                            .collect::<Vec<_>>()
-                                            .join(", ")
-                                    ),
-                                ))
-                            } else {
-                                let s = format!(
-                                    "Box_<({})>",
-                                    calls
-                                        .iter()
-                                        .map(|c| format!("&{}", c.bound_name))
-                                        .collect::<Vec<_>>()
-                                        .join(", ")
-                                );
-                                Some((s.clone(), s))
-                            }
-                        } else {
-                            None
-                        },
+                        tuple_type: (calls.len() > 1).then(|| {
+                            format!(
+                                "({})",
+                                calls
+                                    .iter()
+                                    .map(|c| c.bound_name.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            )
+                        }),
                         callbacks: calls,
                     },
                 );
@@ -208,7 +188,23 @@ impl Builder {
         let ret = self.generate_out_return(&mut uninitialized_vars);
         let (call, ret) = self.apply_outs_mode(call, ret, &mut uninitialized_vars);
 
-        body.push(call);
+        let hash_tables = self.add_hash_tables(env, &mut body);
+        if hash_tables.is_empty() {
+            body.push(call);
+        } else if ret.is_none() && self.ret.ret.parameter.is_some() {
+            // The returned value is kept while the tables are released
+            body.push(Chunk::Let {
+                name: "ret".into(),
+                is_mut: false,
+                value: Box::new(call),
+                type_: None,
+            });
+            body.extend(hash_tables);
+            body.push(Chunk::Custom("ret".into()));
+        } else {
+            body.push(call);
+            body.extend(hash_tables);
+        }
         if let Some(chunk) = ret {
             body.push(chunk);
         }
@@ -219,45 +215,32 @@ impl Builder {
         self.add_assertion(&mut chunks);
 
         if !self.callbacks.is_empty() || !self.destroys.is_empty() {
-            // Key: user data index
-            // Value: the current pos in the tuple for the given argument.
-            let mut poses = HashMap::with_capacity(group_by_user_data.len());
             for trampoline in &self.callbacks {
-                *poses
-                    .entry(&trampoline.user_data_index)
-                    .or_insert_with(|| 0) += 1;
-            }
-            let mut poses = poses
-                .into_iter()
-                .filter(|(_, x)| *x > 1)
-                .map(|(x, _)| (x, 0))
-                .collect::<HashMap<_, _>>();
-            for trampoline in &self.callbacks {
-                let user_data_index = trampoline.user_data_index;
-                let pos = poses.entry(&trampoline.user_data_index);
+                let group = &group_by_user_data[&trampoline.user_data_index];
+                // Position of the closure in the tuple
+                let pos = group.tuple_type.as_ref().and_then(|_| {
+                    group
+                        .callbacks
+                        .iter()
+                        .position(|c| c.name == trampoline.name)
+                });
                 self.add_trampoline(
                     env,
                     &mut chunks,
                     trampoline,
-                    &group_by_user_data[&user_data_index].full_type,
-                    match pos {
-                        Entry::Occupied(ref x) => Some(*x.get()),
-                        _ => None,
-                    },
+                    &group.tuple_type,
+                    pos,
                     bounds,
                     bounds_names,
                     false,
                 );
-                pos.and_modify(|x| {
-                    *x += 1;
-                });
             }
             for destroy in &self.destroys {
                 self.add_trampoline(
                     env,
                     &mut chunks,
                     destroy,
-                    &group_by_user_data[&destroy.user_data_index].full_type,
+                    &group_by_user_data[&destroy.user_data_index].tuple_type,
                     None, // doesn't matter for destroy
                     bounds,
                     bounds_names,
@@ -266,62 +249,41 @@ impl Builder {
             }
             for FuncParameter {
                 pos,
-                full_type,
+                tuple_type,
                 callbacks: calls,
             } in group_by_user_data.values()
             {
-                if calls.len() > 1 {
-                    chunks.push(Chunk::Let {
-                        name: format!("super_callback{pos}"),
-                        is_mut: false,
-                        value: Box::new(Chunk::Custom(if poses.is_empty() {
-                            format!(
-                                "Box_::new(Box_::new(({})))",
-                                calls
-                                    .iter()
-                                    .map(|c| format!("{}_data", c.name))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            )
-                        } else if calls.iter().all(|c| c.scope.is_call()) {
-                            format!(
-                                "&({})",
-                                calls
-                                    .iter()
-                                    .map(|c| format!("{}_data", c.name))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            )
+                let (value, type_) = match tuple_type {
+                    Some(tuple_type) => {
+                        let data = calls
+                            .iter()
+                            .map(|c| format!("{}_data", c.name))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if calls.iter().all(|c| c.scope.is_call()) {
+                            (format!("&mut ({data})"), format!("&mut {tuple_type}"))
                         } else {
-                            format!(
-                                "Box_::new(({}))",
-                                calls
-                                    .iter()
-                                    .map(|c| format!("{}_data", c.name))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
+                            (
+                                format!("Box_::new(({data}))"),
+                                format!("Box_<{tuple_type}>"),
                             )
-                        })),
-                        type_: Some(Box::new(Chunk::Custom(
-                            full_type.clone().map(|x| x.0).unwrap(),
-                        ))),
-                    });
-                } else if !calls.is_empty() {
-                    chunks.push(Chunk::Let {
-                        name: format!("super_callback{pos}"),
-                        is_mut: false,
-                        value: Box::new(Chunk::Custom(format!(
-                            "{}{}_data",
-                            if calls[0].scope.is_call() { "&" } else { "" },
-                            calls[0].name
-                        ))),
-                        type_: Some(Box::new(Chunk::Custom(if calls[0].scope.is_call() {
-                            format!("&{}", calls[0].bound_name)
-                        } else {
-                            format!("Box_<{}>", calls[0].bound_name)
-                        }))),
-                    });
-                }
+                        }
+                    }
+                    None if calls[0].scope.is_call() => (
+                        format!("&{}_data", calls[0].name),
+                        format!("&{}", calls[0].bound_name),
+                    ),
+                    None => (
+                        format!("{}_data", calls[0].name),
+                        format!("Box_<{}>", calls[0].bound_name),
+                    ),
+                };
+                chunks.push(Chunk::Let {
+                    name: format!("super_callback{pos}"),
+                    is_mut: false,
+                    value: Box::new(Chunk::Custom(value)),
+                    type_: Some(Box::new(Chunk::Custom(type_))),
+                });
             }
         } else if let Some(ref trampoline) = self.async_trampoline {
             self.add_async_trampoline(env, &mut chunks, trampoline);
@@ -335,6 +297,34 @@ impl Builder {
         Chunk::BlockHalf(chunks)
     }
 
+    /// Fills the tables passed as `GHashTable`s, returns the statements
+    /// releasing the ones still owned after the call.
+    fn add_hash_tables(&self, env: &Env, body: &mut Vec<Chunk>) -> Vec<Chunk> {
+        let mut unrefs = Vec::new();
+        for trans in &self.transformations {
+            if let TransformationType::ToGlibHashTable {
+                ref name,
+                typ,
+                transfer,
+            } = trans.transformation_type
+            {
+                body.push(Chunk::Let {
+                    name: name.clone(),
+                    is_mut: false,
+                    value: Box::new(Chunk::Custom(hash_table_to_glib(env, name, typ))),
+                    type_: None,
+                });
+                if transfer == library::Transfer::None {
+                    unrefs.push(Chunk::Custom(format!(
+                        "{}({name});",
+                        use_glib_type(env, "ffi::g_hash_table_unref")
+                    )));
+                }
+            }
+        }
+        unrefs
+    }
+
     fn remove_extra_assume_init(
         &self,
         array_length_name: &Option<String>,
@@ -380,28 +370,16 @@ impl Builder {
         env: &Env,
         chunks: &mut Vec<Chunk>,
         trampoline: &Trampoline,
-        full_type: &Option<(String, String)>,
+        tuple_type: &Option<String>,
         pos: Option<usize>,
         bounds: &str,
         bounds_names: &str,
         is_destroy: bool,
     ) {
         if !is_destroy {
-            if full_type.is_none() {
-                if trampoline.scope.is_call() {
-                    chunks.push(Chunk::Custom(format!(
-                        "let {0}_data: {1} = {0};",
-                        trampoline.name, trampoline.bound_name
-                    )));
-                } else {
-                    chunks.push(Chunk::Custom(format!(
-                        "let {0}_data: Box_<{1}> = Box_::new({0});",
-                        trampoline.name, trampoline.bound_name
-                    )));
-                }
-            } else if trampoline.scope.is_call() {
+            if tuple_type.is_none() && !trampoline.scope.is_call() {
                 chunks.push(Chunk::Custom(format!(
-                    "let {0}_data: &{1} = &{0};",
+                    "let {0}_data: Box_<{1}> = Box_::new({0});",
                     trampoline.name, trampoline.bound_name
                 )));
             } else {
@@ -455,86 +433,36 @@ impl Builder {
             .last()
             .map_or_else(|| "Unknown".to_owned(), |p| p.name.clone());
 
-        if let Some(full_type) = full_type {
-            if is_destroy || trampoline.scope.is_async() {
+        if let Some(tuple_type) = tuple_type {
+            let ref_mut = if trampoline.scope.is_call() {
+                "mut "
+            } else {
+                ""
+            };
+            if is_destroy {
                 body.push(Chunk::Let {
-                    name: format!("{}callback", if is_destroy { "_" } else { "" }),
+                    name: "_callback".to_owned(),
                     is_mut: false,
-                    value: Box::new(Chunk::Custom(format!("Box_::from_raw({func} as *mut _)"))),
-                    type_: Some(Box::new(Chunk::Custom(full_type.1.clone()))),
+                    value: Box::new(Chunk::Custom(format!(
+                        "Box_::from_raw({func} as *mut {tuple_type})"
+                    ))),
+                    type_: None,
                 });
             } else {
+                let pos = pos.unwrap_or_default();
                 body.push(Chunk::Let {
                     name: "callback".to_owned(),
                     is_mut: false,
                     value: Box::new(Chunk::Custom(format!(
-                        "{}*({} as *mut _)",
-                        if !trampoline.scope.is_call() {
-                            "&"
-                        } else if pos.is_some() {
-                            "&mut "
-                        } else {
-                            ""
-                        },
-                        func
-                    ))),
-                    type_: Some(Box::new(Chunk::Custom(
-                        if !trampoline.scope.is_async() && !trampoline.scope.is_call() {
-                            format!("&{}", full_type.1)
-                        } else {
-                            full_type.1.clone()
-                        },
+                        "&{ref_mut}*({func} as *mut {tuple_type})"
                     ))),
+                    type_: None,
                 });
-                if trampoline.scope.is_async() {
-                    body.push(Chunk::Custom(format!(
-                        "let callback = callback{}{};",
-                        if let Some(pos) = pos {
-                            format!(".{pos}")
-                        } else {
-                            String::new()
-                        },
-                        if *trampoline.nullable {
-                            ".expect(\"cannot get closure...\")"
-                        } else {
-                            ""
-                        }
-                    )));
-                } else if !trampoline.scope.is_call() {
-                    if *trampoline.nullable {
-                        body.push(Chunk::Custom(format!(
-                            "if let Some(ref callback) = callback{} {{",
-                            if let Some(pos) = pos {
-                                format!(".{pos}")
-                            } else {
-                                String::new()
-                            }
-                        )));
-                    } else {
-                        body.push(Chunk::Custom(format!(
-                            "let callback = callback{}",
-                            if let Some(pos) = pos {
-                                format!(".{pos}")
-                            } else {
-                                String::new()
-                            }
-                        )));
-                    }
-                } else if !trampoline.scope.is_async() && *trampoline.nullable {
-                    body.push(Chunk::Custom(format!(
-                        "if let Some(ref {}callback) = {} {{",
-                        if trampoline.scope.is_call() {
-                            "mut "
-                        } else {
-                            ""
-                        },
-                        if let Some(pos) = pos {
-                            format!("(*callback).{pos}")
-                        } else {
-                            "*callback".to_owned()
-                        }
-                    )));
-                }
+                body.push(Chunk::Custom(if *trampoline.nullable {
+                    format!("if let Some(ref {ref_mut}callback) = callback.{pos} {{")
+                } else {
+                    format!("let callback = &{ref_mut}callback.{pos};")
+                }));
             }
         } else {
             body.push(Chunk::Let {
@@ -826,7 +754,7 @@ impl Builder {
                 name: out.lib_par.name.clone(),
                 is_mut: true,
                 value: Box::new(type_mem_mode(env, &out.lib_par)),
-                type_: None,
+                type_: fixed_array_type(env, out.lib_par.typ),
             });
         body.extend(output_vars);
 
@@ -1074,7 +1002,7 @@ impl Builder {
                     name: parameter.name.clone(),
                     is_mut: true,
                     value: Box::new(val),
-                    type_: None,
+                    type_: fixed_array_type(env, parameter.typ),
                 };
                 body.push(chunk);
             }
@@ -1256,6 +1184,12 @@ fn c_type_mem_mode_lib(
 ) -> OutMemMode {
     use self::OutMemMode::*;
     match ConversionType::of(env, typ) {
+        // Filled in place, like the scalars
+        ConversionType::Pointer
+            if matches!(env.library.type_(typ), library::Type::FixedArray(..)) =>
+        {
+            Uninitialized
+        }
         ConversionType::Pointer => {
             if caller_allocates {
                 UninitializedNamed(RustType::try_new(env, typ).unwrap().into_string())
@@ -1280,6 +1214,21 @@ fn c_type_mem_mode_lib(
     }
 }
 
+/// The type of the uninitialized C array filled by the function, it can't be
+/// inferred from the conversion of its elements.
+fn fixed_array_type(env: &Env, typ: TypeId) -> Option<Box<Chunk>> {
+    match env.library.type_(typ) {
+        library::Type::FixedArray(inner_tid, size, Some(inner_c_type)) => {
+            let inner = ffi_type(env, *inner_tid, inner_c_type).ok()?;
+            Some(Box::new(Chunk::Custom(format!(
+                "std::mem::MaybeUninit<[{}; {size}]>",
+                inner.as_str()
+            ))))
+        }
+        _ => None,
+    }
+}
+
 fn c_type_mem_mode(env: &Env, parameter: &AnalysisCParameter) -> OutMemMode {
     c_type_mem_mode_lib(
         env,
@@ -1291,6 +1240,14 @@ fn c_type_mem_mode(env: &Env, parameter: &AnalysisCParameter) -> OutMemMode {
 
 fn type_mem_mode(env: &Env, parameter: &library::Parameter) -> Chunk {
     match ConversionType::of(env, parameter.typ) {
+        ConversionType::Pointer
+            if matches!(
+                env.library.type_(parameter.typ),
+                library::Type::FixedArray(..)
+            ) =>
+        {
+            Chunk::Uninitialized
+        }
         ConversionType::Pointer => {
             if parameter.caller_allocates {
                 Chunk::UninitializedNamed {
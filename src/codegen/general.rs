@@ -1077,6 +1077,16 @@ pub fn escape_string(s: &str) -> String {
     es
 }
 
+/// Wraps an unsafe operation done in the body of an `unsafe fn`, which
+/// edition 2024 requires to be in its own `unsafe` block.
+pub fn unsafe_in_unsafe_fn(env: &Env, expr: &str) -> String {
+    if env.config.edition.is_2024() {
+        format!("unsafe {{ {expr} }}")
+    } else {
+        expr.to_owned()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
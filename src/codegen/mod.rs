@@ -37,10 +37,13 @@ mod return_value;
 mod signal;
 mod signal_body;
 mod special_functions;
+mod subclass;
 mod sys;
 mod trait_impls;
 mod trampoline;
 mod trampoline_from_glib;
+mod union;
+mod unions;
 mod visibility;
 pub use visibility::Visibility;
 mod trampoline_to_glib;
@@ -64,7 +67,9 @@ fn normal_generate(env: &Env) {
 
     generate_single_version_file(env);
     objects::generate(env, root_path, &mut mod_rs, &mut traits, &mut builders);
+    subclass::generate(env, root_path, &mut mod_rs);
     records::generate(env, root_path, &mut mod_rs);
+    unions::generate(env, root_path, &mut mod_rs);
     enums::generate(env, root_path, &mut mod_rs);
     flags::generate(env, root_path, &mut mod_rs);
     alias::generate(env, root_path, &mut mod_rs);
@@ -72,6 +77,7 @@ fn normal_generate(env: &Env) {
     constants::generate(env, root_path, &mut mod_rs);
 
     generate_mod_rs(env, root_path, &mod_rs, &traits, &builders);
+    remove_stale_files(env, root_path);
 }
 
 pub fn generate_mod_rs(
@@ -82,7 +88,7 @@ pub fn generate_mod_rs(
     builders: &[String],
 ) {
     let path = root_path.join("mod.rs");
-    save_to_file(path, env.config.make_backup, |w| {
+    save_to_file(env, path, |w| {
         general::start_comments(w, &env.config)?;
         general::write_vec(w, mod_rs)?;
         writeln!(w)?;
@@ -103,7 +109,7 @@ pub fn generate_mod_rs(
 
 pub fn generate_single_version_file(env: &Env) {
     if let Some(ref path) = env.config.single_version_file {
-        save_to_file(path, env.config.make_backup, |w| {
+        save_to_file(env, path, |w| {
             general::single_version_file(w, &env.config, "")
         });
     }
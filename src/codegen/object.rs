@@ -329,9 +329,155 @@ pub fn generate(w: &mut dyn Write, env: &Env, analysis: &analysis::object::Info)
         writeln!(w)?;
         generate_trait(w, env, analysis)?;
     }
+
+    if config.generate_property_default_tests && !analysis.is_interface {
+        writeln!(w)?;
+        generate_property_default_tests(w, env, analysis)?;
+    }
     Ok(())
 }
 
+/// Generates a test comparing the default value of the `ParamSpec`s with the
+/// ones from the GIR file.
+fn generate_property_default_tests(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::object::Info,
+) -> Result<()> {
+    let mut checks = BTreeMap::new();
+    let own_builder_properties = analysis
+        .builder_properties
+        .iter()
+        .filter(|(_, tid)| *tid == analysis.type_id)
+        .flat_map(|(props, _)| props);
+    for prop in own_builder_properties.chain(&analysis.properties) {
+        if checks.contains_key(&prop.name) {
+            continue;
+        }
+        let Some(default_value) = &prop.default_value else {
+            continue;
+        };
+        if let Some(check) = property_default_check(env, &prop.name, prop.typ, default_value) {
+            checks.insert(prop.name.clone(), (prop.version, check));
+        }
+    }
+    if checks.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        w,
+        "#[cfg(test)]
+mod tests {{
+    use super::*;
+    use {prelude};
+
+    #[test]
+    fn property_defaults() {{
+        let class = {class}::<{name}>::from_type({name}::static_type()).unwrap();
+        let default_value = |name| class.find_property(name).unwrap().default_value().clone();",
+        prelude = nameutil::use_glib_type(env, "prelude::*"),
+        class = nameutil::use_glib_type(env, "Class"),
+        name = analysis.name,
+    )?;
+    for (version, check) in checks.into_values() {
+        version_condition_no_doc(w, env, None, version, false, 2)?;
+        writeln!(w, "\t\t{check};")?;
+    }
+    writeln!(w, "\t}}\n}}")
+}
+
+/// Returns the assertion comparing the runtime default value of the property
+/// with its GIR default value, for the types that can be compared.
+fn property_default_check(
+    env: &Env,
+    name: &str,
+    typ: library::TypeId,
+    default_value: &str,
+) -> Option<String> {
+    use crate::library::{Basic, Type};
+
+    let value = |get_type: &str| format!("default_value(\"{name}\").get::<{get_type}>().unwrap()");
+    let full_name = || {
+        if typ.ns_id == library::MAIN_NAMESPACE {
+            Some(format!("crate::{}", env.type_(typ).get_name()))
+        } else {
+            env.symbols
+                .borrow()
+                .by_tid(typ)
+                .map(|symbol| symbol.full_rust_name())
+        }
+    };
+
+    let (get_type, expected) = match env.type_(typ) {
+        Type::Basic(Basic::Boolean) => {
+            return match default_value {
+                "TRUE" => Some(format!("assert!({})", value("bool"))),
+                "FALSE" => Some(format!("assert!(!{})", value("bool"))),
+                _ => None,
+            };
+        }
+        Type::Basic(
+            Basic::Int8
+            | Basic::UInt8
+            | Basic::Int32
+            | Basic::UInt32
+            | Basic::Int64
+            | Basic::UInt64
+            | Basic::Int
+            | Basic::UInt,
+        ) => {
+            default_value.parse::<i128>().ok()?;
+            let rust_type = RustType::try_new(env, typ).ok()?.into_string();
+            (rust_type, default_value.to_owned())
+        }
+        Type::Basic(Basic::Utf8) => {
+            let expected = if default_value == "NULL" {
+                "None".to_owned()
+            } else {
+                format!("Some({default_value:?})")
+            };
+            return Some(format!(
+                "assert_eq!({}.as_deref(), {expected})",
+                value("Option<String>")
+            ));
+        }
+        Type::Enumeration(enum_) => {
+            let member = enum_
+                .members
+                .iter()
+                .find(|m| m.c_identifier == default_value)?;
+            let full_name = full_name()?;
+            let expected = format!("{full_name}::{}", nameutil::enum_member_name(&member.name));
+            (full_name, expected)
+        }
+        Type::Bitfield(flags) => {
+            let full_name = full_name()?;
+            let expected = if default_value == "0" {
+                format!("{full_name}::empty()")
+            } else {
+                default_value
+                    .split('|')
+                    .map(|value| {
+                        let member = flags
+                            .members
+                            .iter()
+                            .find(|m| m.c_identifier == value.trim())?;
+                        Some(format!(
+                            "{full_name}::{}",
+                            nameutil::bitfield_member_name(&member.name)
+                        ))
+                    })
+                    .collect::<Option<Vec<_>>>()?
+                    .join(" | ")
+            };
+            (full_name, expected)
+        }
+        _ => return None,
+    };
+    Some(format!("assert_eq!({}, {expected})", value(&get_type)))
+}
+
 fn generate_builder(w: &mut dyn Write, env: &Env, analysis: &analysis::object::Info) -> Result<()> {
     let glib_crate_name = if env.namespaces.is_glib_crate {
         "crate"
@@ -422,7 +568,10 @@ fn generate_builder(w: &mut dyn Write, env: &Env, analysis: &analysis::object::I
             if let Some(param_type_override) = param_type_override {
                 param_type_str = param_type_override.to_string();
             }
-            let name = nameutil::mangle_keywords(nameutil::signal_to_snake(&property.name));
+            let name = nameutil::mangle_keywords(
+                nameutil::signal_to_snake(&property.name),
+                env.config.edition,
+            );
 
             let version_condition_string =
                 version_condition_string(env, Some(super_tid.ns_id), property.version, false, 1);
@@ -18,16 +18,18 @@ pub fn generate(
             continue;
         }
 
-        let mod_name = obj
-            .module_name
-            .clone()
-            .unwrap_or_else(|| module_name(split_namespace_name(&class_analysis.full_name).1));
+        let mod_name = obj.module_name.clone().unwrap_or_else(|| {
+            module_name(
+                split_namespace_name(&class_analysis.full_name).1,
+                env.config.edition,
+            )
+        });
 
         let mut path = root_path.join(&mod_name);
         path.set_extension("rs");
         info!("Generating file {:?}", path);
 
-        save_to_file(path, env.config.make_backup, |w| {
+        save_to_file(env, path, |w| {
             super::object::generate(w, env, class_analysis)
         });
 
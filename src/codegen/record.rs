@@ -1,11 +1,15 @@
 use std::io::{Result, Write};
 
-use super::{function, general, trait_impls};
+use super::{function, general, trait_impls, translate_from_glib::TranslateFromGlib};
 use crate::{
-    analysis::{self, record_type::RecordType, special_functions::Type},
+    analysis::{
+        self, conversion_type::ConversionType, record_type::RecordType, special_functions::Type,
+    },
+    chunk::conversion_from_glib::Mode,
     env::Env,
     library,
     traits::MaybeRef,
+    version::Version,
 };
 
 pub fn generate(w: &mut dyn Write, env: &Env, analysis: &analysis::record::Info) -> Result<()> {
@@ -91,10 +95,11 @@ pub fn generate(w: &mut dyn Write, env: &Env, analysis: &analysis::record::Info)
         );
     }
 
-    if analysis
-        .functions
-        .iter()
-        .any(|f| f.status.need_generate() && !f.hidden)
+    if !analysis.fields.is_empty()
+        || analysis
+            .functions
+            .iter()
+            .any(|f| f.status.need_generate() && !f.hidden)
     {
         writeln!(w)?;
         write!(w, "impl {} {{", analysis.name)?;
@@ -113,6 +118,10 @@ pub fn generate(w: &mut dyn Write, env: &Env, analysis: &analysis::record::Info)
             )?;
         }
 
+        for field in &analysis.fields {
+            generate_field_accessors(w, env, analysis, field)?;
+        }
+
         writeln!(w, "}}")?;
     }
 
@@ -147,9 +156,66 @@ pub fn generate(w: &mut dyn Write, env: &Env, analysis: &analysis::record::Info)
     Ok(())
 }
 
-pub fn generate_reexports(
+fn generate_field_accessors(
+    w: &mut dyn Write,
     env: &Env,
     analysis: &analysis::record::Info,
+    field: &analysis::fields::Info,
+) -> Result<()> {
+    let access = if analysis.boxed_inline {
+        format!("self.inner.{}", field.name)
+    } else {
+        format!("(*self.as_ptr()).{}", field.name)
+    };
+    let (prefix, suffix) = Mode {
+        typ: field.typ,
+        transfer: library::Transfer::None,
+        try_from_glib: Default::default(),
+    }
+    .translate_from_glib_as_function(env, None);
+    let value = format!("{prefix}{access}{suffix}");
+    let value = if analysis.boxed_inline && field.conversion_type == ConversionType::Direct {
+        value
+    } else {
+        format!("unsafe {{ {value} }}")
+    };
+    let version = Version::if_stricter_than(field.version, analysis.version);
+
+    writeln!(w)?;
+    general::cfg_condition(w, field.cfg_condition.as_ref(), false, 1)?;
+    general::version_condition(w, env, None, version, false, 1)?;
+    writeln!(
+        w,
+        "\tpub fn {}(&self) -> {} {{",
+        field.name, field.rust_type
+    )?;
+    writeln!(w, "\t\t{value}")?;
+    writeln!(w, "\t}}")?;
+
+    if let Some(ref setter_name) = field.setter_name {
+        let value = if field.conversion_type == ConversionType::Scalar {
+            format!("{}.into_glib()", field.name)
+        } else {
+            field.name.clone()
+        };
+        writeln!(w)?;
+        general::cfg_condition(w, field.cfg_condition.as_ref(), false, 1)?;
+        general::version_condition(w, env, None, version, false, 1)?;
+        writeln!(
+            w,
+            "\tpub fn {}(&mut self, {}: {}) {{",
+            setter_name, field.name, field.rust_type
+        )?;
+        writeln!(w, "\t\t{access} = {value};")?;
+        writeln!(w, "\t}}")?;
+    }
+
+    Ok(())
+}
+
+pub fn generate_reexports(
+    env: &Env,
+    analysis: &analysis::info_base::InfoBase,
     module_name: &str,
     contents: &mut Vec<String>,
 ) {
@@ -3,8 +3,9 @@ use std::cmp;
 use crate::{
     analysis::{
         self, conversion_type::ConversionType, namespaces, out_parameters::Mode,
-        rust_type::RustType, try_from_glib::TryFromGlib,
+        return_value::ZeroCopy, rust_type::RustType, try_from_glib::TryFromGlib,
     },
+    codegen::translate_from_glib::hash_table_element_type,
     env::Env,
     library::{self, ParameterDirection, TypeId},
     nameutil::{is_gstring, mangle_keywords, use_glib_type},
@@ -58,6 +59,30 @@ impl ToReturnValue for analysis::return_value::Info {
         is_trampoline: bool,
     ) -> Option<String> {
         let par = self.parameter.as_ref()?;
+        if self.zero_copy == Some(ZeroCopy::Array) {
+            if let library::Type::Array(inner_tid) = env.type_(par.lib_par.typ) {
+                return Some(format!(
+                    "{}<{}>",
+                    use_glib_type(env, "Slice"),
+                    RustType::try_new(env, *inner_tid).into_string()
+                ));
+            }
+        }
+        if self.borrow {
+            if let library::Type::HashTable(key_tid, value_tid) = env.type_(par.lib_par.typ) {
+                return Some(format!(
+                    "std::collections::HashMap<{}, {}>",
+                    hash_table_element_type(env, *key_tid, true),
+                    hash_table_element_type(env, *value_tid, true)
+                ));
+            }
+            let type_name = format!("&{}", borrowed_type(env, par.lib_par.typ));
+            return Some(if *par.lib_par.nullable {
+                format!("Option<{type_name}>")
+            } else {
+                type_name
+            });
+        }
         par.lib_par
             .to_return_value(env, try_from_glib, is_trampoline)
             .map(|type_name| {
@@ -79,8 +104,17 @@ impl ToReturnValue for analysis::return_value::Info {
     }
 }
 
+/// Type referenced by a borrowed return value, the lifetime is the one of
+/// `&self`.
+pub fn borrowed_type(env: &Env, typ: TypeId) -> String {
+    match env.type_(typ) {
+        library::Type::Basic(library::Basic::Utf8) => use_glib_type(env, "GStr"),
+        _ => RustType::try_new(env, typ).into_string(),
+    }
+}
+
 /// Returns the `TypeId` of the returned types from the provided function.
-pub fn out_parameter_types(analysis: &analysis::functions::Info) -> Vec<TypeId> {
+pub fn out_parameter_types(env: &Env, analysis: &analysis::functions::Info) -> Vec<TypeId> {
     // If it returns an error, there is nothing for us to check.
     if analysis.ret.bool_return_is_error.is_some()
         || analysis.ret.nullable_return_is_error.is_some()
@@ -117,8 +151,10 @@ pub fn out_parameter_types(analysis: &analysis::functions::Info) -> Vec<TypeId>
                 for out in analysis.outs.iter().filter(|out| !out.lib_par.is_error) {
                     // The actual return value is inserted with an empty name at position 0
                     if !out.lib_par.name.is_empty() {
-                        let mangled_par_name =
-                            crate::nameutil::mangle_keywords(out.lib_par.name.as_str());
+                        let mangled_par_name = crate::nameutil::mangle_keywords(
+                            out.lib_par.name.as_str(),
+                            env.config.edition,
+                        );
                         let param_pos = analysis
                             .parameters
                             .c_parameters
@@ -226,7 +262,7 @@ pub fn out_parameters_as_return(env: &Env, analysis: &analysis::functions::Info)
     {
         // The actual return value is inserted with an empty name at position 0
         if !out.lib_par.name.is_empty() {
-            let mangled_par_name = mangle_keywords(out.lib_par.name.as_str());
+            let mangled_par_name = mangle_keywords(out.lib_par.name.as_str(), env.config.edition);
             let param_pos = analysis
                 .parameters
                 .c_parameters
@@ -249,7 +285,14 @@ pub fn out_parameters_as_return(env: &Env, analysis: &analysis::functions::Info)
         if pos > skip {
             return_str.push_str(", ");
         }
-        let s = out_parameter_as_return(out, env);
+        // The buffer lives as long as the instance
+        let s = if out.lib_par.name.is_empty()
+            && analysis.ret.zero_copy == Some(ZeroCopy::BorrowedBuffer)
+        {
+            "&[u8]".to_owned()
+        } else {
+            out_parameter_as_return(out, env)
+        };
         return_str.push_str(&s);
     }
     return_str.push_str(&suffix);
@@ -258,8 +301,14 @@ pub fn out_parameters_as_return(env: &Env, analysis: &analysis::functions::Info)
 
 fn out_parameter_as_return(out: &analysis::Parameter, env: &Env) -> String {
     // TODO: upcasts?
+    // Fixed-size arrays can only be filled through out parameters
+    let direction = if matches!(env.type_(out.lib_par.typ), library::Type::FixedArray(..)) {
+        out.lib_par.direction
+    } else {
+        ParameterDirection::Return
+    };
     let name = RustType::builder(env, out.lib_par.typ)
-        .direction(ParameterDirection::Return)
+        .direction(direction)
         .nullable(out.lib_par.nullable)
         .scope(out.lib_par.scope)
         .try_from_glib(&out.try_from_glib)
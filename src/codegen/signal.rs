@@ -24,7 +24,7 @@ pub fn generate(
     let comment_prefix = if commented { "//" } else { "" };
     let pub_prefix = if in_trait { "" } else { "pub " };
 
-    let function_type = function_type_string(env, analysis, true);
+    let function_type = function_type_string(env, analysis, true, false);
     let declaration = declaration(analysis, &function_type);
     let suffix = if only_declaration { ";" } else { " {" };
 
@@ -86,14 +86,16 @@ pub fn generate(
         return Ok(());
     }
 
-    if let Some(ref emit_name) = analysis.action_emit_name {
+    if let Some(ref emit_name) = analysis.emit_name {
         writeln!(w)?;
         if !in_trait || only_declaration {
             cfg_deprecated(w, env, None, analysis.deprecated_version, commented, indent)?;
         }
         version_condition(w, env, None, analysis.version, commented, indent)?;
 
-        let function_type = function_type_string(env, analysis, false);
+        // The existing emitters of action signals keep their signature
+        let with_detail = analysis.is_detailed && !analysis.is_action;
+        let function_type = function_type_string(env, analysis, false, with_detail);
 
         writeln!(
             w,
@@ -128,20 +130,43 @@ pub fn generate(
                 args.push_str(&par.name);
             }
 
-            if trampoline.ret.typ != Default::default() {
+            let signal_name = if with_detail {
                 writeln!(
                     w,
-                    "{}self.emit_by_name(\"{}\", &[{}])",
+                    "{}let signal_name = detail.map_or_else(|| \"{}\".to_owned(), |detail| format!(\"{}::{{detail}}\"));",
                     tabs(indent + 1),
                     analysis.signal_name,
+                    analysis.signal_name,
+                )?;
+                "&signal_name".to_owned()
+            } else {
+                format!("\"{}\"", analysis.signal_name)
+            };
+
+            if trampoline.ret.typ == Default::default() {
+                writeln!(
+                    w,
+                    "{}self.emit_by_name::<()>({}, &[{}]);",
+                    tabs(indent + 1),
+                    signal_name,
+                    args,
+                )?;
+            } else if trampoline.inhibit && !analysis.is_action {
+                // The handlers return a gboolean, `true` stopping the emission.
+                // The emitters of action signals keep returning the value as is.
+                writeln!(
+                    w,
+                    "{}self.emit_by_name::<bool>({}, &[{}]).into()",
+                    tabs(indent + 1),
+                    signal_name,
                     args,
                 )?;
             } else {
                 writeln!(
                     w,
-                    "{}self.emit_by_name::<()>(\"{}\", &[{}]);",
+                    "{}self.emit_by_name({}, &[{}])",
                     tabs(indent + 1),
-                    analysis.signal_name,
+                    signal_name,
                     args,
                 )?;
             }
@@ -156,6 +181,7 @@ fn function_type_string(
     env: &Env,
     analysis: &analysis::signals::Info,
     closure: bool,
+    detail: bool,
 ) -> Option<String> {
     analysis.trampoline.as_ref().ok()?;
 
@@ -171,6 +197,7 @@ fn function_type_string(
         trampoline,
         Some(if closure { "Self" } else { "self" }),
         closure,
+        detail,
     );
     Some(type_)
 }
@@ -0,0 +1,448 @@
+use std::{
+    io::{Result, Write},
+    path::Path,
+};
+
+use log::info;
+
+use super::{
+    general::{
+        self, allow_deprecated, cfg_condition, cfg_condition_no_doc, cfg_deprecated, doc_hidden,
+        version_condition, version_condition_no_doc,
+    },
+    trampoline::{func_string, trampoline_parameters, trampoline_returns},
+    trampoline_from_glib::TrampolineFromGlib,
+    trampoline_to_glib::TrampolineToGlib,
+};
+use crate::{
+    analysis::{
+        self, conversion_type::ConversionType, trampolines::Trampoline, virtual_methods::Subclass,
+    },
+    env::Env,
+    file_saver::save_to_file,
+    library,
+    nameutil::{module_name, split_namespace_name, use_glib_type},
+    version::Version,
+    writer::primitives::tabs,
+};
+
+pub fn generate(env: &Env, root_path: &Path, mod_rs: &mut Vec<String>) {
+    info!("Generate subclass modules");
+    let root_path = root_path.join("subclass");
+    let mut subclass_mod_rs = Vec::new();
+    let mut prelude = Vec::new();
+
+    for analysis in env.analysis.objects.values() {
+        let obj = &env.config.objects[&analysis.full_name];
+        if !obj.status.need_generate() {
+            continue;
+        }
+        let Some(ref subclass) = analysis.subclass else {
+            continue;
+        };
+
+        let mod_name = obj.module_name.clone().unwrap_or_else(|| {
+            module_name(
+                split_namespace_name(&analysis.full_name).1,
+                env.config.edition,
+            )
+        });
+
+        let mut path = root_path.join(&mod_name);
+        path.set_extension("rs");
+        info!("Generating file {:?}", path);
+
+        save_to_file(env, path, |w| generate_subclass(w, env, analysis, subclass));
+
+        let mut cfgs = Vec::new();
+        if let Some(cfg) = general::cfg_condition_string(analysis.cfg_condition.as_ref(), false, 0)
+        {
+            cfgs.push(cfg);
+        }
+        if let Some(cfg) = general::version_condition_string(env, None, analysis.version, false, 0)
+        {
+            cfgs.push(cfg);
+        }
+
+        subclass_mod_rs.push(String::new());
+        subclass_mod_rs.extend_from_slice(&cfgs);
+        subclass_mod_rs.push(format!("mod {mod_name};"));
+
+        for cfg in &cfgs {
+            prelude.push(format!("\t{cfg}"));
+        }
+        prelude.push(format!(
+            "\tpub use super::{}::{{{}, {}}};",
+            mod_name,
+            subclass.impl_trait_name,
+            subclass.impl_ext_trait_name(),
+        ));
+    }
+
+    if prelude.is_empty() {
+        return;
+    }
+
+    save_to_file(env, root_path.join("mod.rs"), |w| {
+        general::start_comments(w, &env.config)?;
+        general::write_vec(w, &subclass_mod_rs)?;
+        writeln!(w)?;
+        writeln!(w, "pub(crate) mod prelude {{")?;
+        general::write_vec(w, &prelude)?;
+        writeln!(w, "}}")
+    });
+
+    mod_rs.push(String::new());
+    mod_rs.push("pub(crate) mod subclass;".to_owned());
+}
+
+fn generate_subclass(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::object::Info,
+    subclass: &Subclass,
+) -> Result<()> {
+    general::start_comments(w, &env.config)?;
+    if subclass
+        .virtual_methods
+        .iter()
+        .any(|vfunc| vfunc.deprecated_version.is_some())
+    {
+        writeln!(w, "#![allow(deprecated)]")?;
+    }
+    general::uses(w, env, &subclass.imports, analysis.version)?;
+
+    let vfuncs = subclass
+        .virtual_methods
+        .iter()
+        .filter(|vfunc| vfunc.status.need_generate())
+        .collect::<Vec<_>>();
+
+    writeln!(w)?;
+    write!(
+        w,
+        "pub trait {}: {} {{",
+        subclass.impl_trait_name, subclass.parent_impl_trait_name
+    )?;
+    for vfunc in &vfuncs {
+        generate_impl_method(w, env, analysis, vfunc)?;
+    }
+    writeln!(w, "}}")?;
+
+    writeln!(w)?;
+    writeln!(
+        w,
+        "mod sealed {{
+    pub trait Sealed {{}}
+    impl<T: super::{}> Sealed for T {{}}
+}}",
+        subclass.impl_trait_name,
+    )?;
+
+    writeln!(w)?;
+    write!(
+        w,
+        "pub trait {}: sealed::Sealed + ObjectSubclass {{",
+        subclass.impl_ext_trait_name()
+    )?;
+    for vfunc in &vfuncs {
+        if let Ok(ref trampoline) = vfunc.trampoline {
+            generate_parent_method(w, env, analysis, subclass, vfunc, trampoline)?;
+        }
+    }
+    writeln!(w, "}}")?;
+
+    writeln!(w)?;
+    writeln!(
+        w,
+        "impl<T: {}> {} for T {{}}",
+        subclass.impl_trait_name,
+        subclass.impl_ext_trait_name()
+    )?;
+
+    writeln!(w)?;
+    if analysis.is_interface {
+        writeln!(
+            w,
+            "unsafe impl<T: {}> IsImplementable<T> for {} {{
+    fn interface_init(iface: &mut {}) {{
+        let klass = iface.as_mut();",
+            subclass.impl_trait_name,
+            analysis.name,
+            use_glib_type(env, "Interface<Self>"),
+        )?;
+    } else {
+        writeln!(
+            w,
+            "unsafe impl<T: {}> IsSubclassable<T> for {} {{
+    fn class_init(class: &mut {}) {{
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();",
+            subclass.impl_trait_name,
+            analysis.name,
+            use_glib_type(env, "Class<Self>"),
+        )?;
+    }
+    for vfunc in &vfuncs {
+        if let Ok(ref trampoline) = vfunc.trampoline {
+            if let Some((signal_name, when)) = &vfunc.class_handler_of {
+                writeln!(
+                    w,
+                    "{}// Overrides the class handler of the \"{}\" signal, run {}",
+                    tabs(2),
+                    signal_name,
+                    match when {
+                        library::SignalEmissionStage::First => "first",
+                        library::SignalEmissionStage::Last => "last",
+                        library::SignalEmissionStage::Cleanup => "at cleanup",
+                    }
+                )?;
+            }
+            let version = Version::if_stricter_than(vfunc.version, analysis.version);
+            version_condition_no_doc(w, env, None, version, false, 2)?;
+            cfg_condition_no_doc(w, vfunc.cfg_condition.as_ref(), false, 2)?;
+            // Attributes aren't allowed on expression statements
+            let conditional = version.is_some() || vfunc.cfg_condition.is_some();
+            if conditional {
+                writeln!(w, "{}{{", tabs(2))?;
+            }
+            writeln!(
+                w,
+                "{}klass.{} = Some({}::<T>);",
+                tabs(if conditional { 3 } else { 2 }),
+                vfunc.vfunc_name,
+                trampoline.name
+            )?;
+            if conditional {
+                writeln!(w, "{}}}", tabs(2))?;
+            }
+        }
+    }
+    writeln!(w, "\t}}\n}}")?;
+
+    for vfunc in &vfuncs {
+        if let Ok(ref trampoline) = vfunc.trampoline {
+            writeln!(w)?;
+            generate_trampoline(w, env, analysis, subclass, vfunc, trampoline)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn method_attributes(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::object::Info,
+    vfunc: &analysis::virtual_methods::Info,
+    commented: bool,
+    indent: usize,
+) -> Result<()> {
+    let comment_prefix = if commented { "//" } else { "" };
+    let version = Version::if_stricter_than(vfunc.version, analysis.version);
+    cfg_deprecated(w, env, None, vfunc.deprecated_version, commented, indent)?;
+    cfg_condition(w, vfunc.cfg_condition.as_ref(), commented, indent)?;
+    version_condition(w, env, None, version, commented, indent)?;
+    doc_hidden(w, vfunc.doc_hidden, comment_prefix, indent)?;
+    allow_deprecated(w, vfunc.deprecated_version, commented, indent)
+}
+
+fn generate_impl_method(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::object::Info,
+    vfunc: &analysis::virtual_methods::Info,
+) -> Result<()> {
+    let unsafe_ = if vfunc.unsafe_ { "unsafe " } else { "" };
+
+    writeln!(w)?;
+    match vfunc.trampoline {
+        Ok(ref trampoline) => {
+            method_attributes(w, env, analysis, vfunc, false, 1)?;
+            writeln!(
+                w,
+                "\t{}fn {}{} {{",
+                unsafe_,
+                vfunc.name,
+                func_string(env, trampoline, Some("self"), false, false),
+            )?;
+            writeln!(
+                w,
+                "\t\tself.{}({})",
+                vfunc.parent_name(),
+                call_arguments(trampoline)
+            )?;
+            writeln!(w, "\t}}")
+        }
+        Err(ref errors) => {
+            method_attributes(w, env, analysis, vfunc, true, 1)?;
+            writeln!(w, "\t//{}fn {}(&self) {{", unsafe_, vfunc.name)?;
+            for error in errors {
+                writeln!(w, "\t//\t{error}")?;
+            }
+            writeln!(w, "\t//}}")
+        }
+    }
+}
+
+fn generate_parent_method(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::object::Info,
+    subclass: &Subclass,
+    vfunc: &analysis::virtual_methods::Info,
+    trampoline: &Trampoline,
+) -> Result<()> {
+    let unsafe_ = if vfunc.unsafe_ { "unsafe " } else { "" };
+    let has_return = trampoline.ret.typ != Default::default();
+    let c_class_type = format!("{}::{}", env.main_sys_crate_name(), subclass.c_class_type);
+
+    writeln!(w)?;
+    method_attributes(w, env, analysis, vfunc, false, 1)?;
+    writeln!(
+        w,
+        "\t{}fn {}{} {{",
+        unsafe_,
+        vfunc.parent_name(),
+        func_string(env, trampoline, Some("self"), false, false),
+    )?;
+    writeln!(w, "\t\tunsafe {{")?;
+    writeln!(w, "\t\t\tlet data = Self::type_data();")?;
+    if analysis.is_interface {
+        writeln!(
+            w,
+            "\t\t\tlet parent_class = data.as_ref().parent_interface::<{}>() as *const {};",
+            analysis.name, c_class_type,
+        )?;
+    } else {
+        writeln!(
+            w,
+            "\t\t\tlet parent_class = data.as_ref().parent_class() as *const {c_class_type};",
+        )?;
+    }
+
+    let mut args = vec![format!(
+        "self.obj().unsafe_cast_ref::<{}>().to_glib_none().0",
+        analysis.name
+    )];
+    for transformation in trampoline.parameters.transformations.iter().skip(1) {
+        let name = &transformation.name;
+        args.push(match transformation.conversion_type {
+            ConversionType::Direct => name.clone(),
+            ConversionType::Scalar | ConversionType::Option | ConversionType::Result { .. } => {
+                format!("{name}.into_glib()")
+            }
+            ConversionType::Borrow | ConversionType::Pointer => {
+                if transformation.transfer == library::Transfer::Full {
+                    format!("{name}.to_glib_full()")
+                } else {
+                    format!("{name}.to_glib_none().0")
+                }
+            }
+            ConversionType::Unknown => format!("/*Unknown conversion*/{name}"),
+        });
+    }
+    let call = format!("f({})", args.join(", "));
+
+    if has_return {
+        writeln!(
+            w,
+            "\t\t\tlet f = (*parent_class)\n\t\t\t\t.{}\n\t\t\t\t.expect(\"No parent class implementation for \\\"{}\\\"\");",
+            vfunc.vfunc_name, vfunc.vfunc_name,
+        )?;
+        let call = match ConversionType::of(env, trampoline.ret.typ) {
+            ConversionType::Direct => call,
+            ConversionType::Pointer => format!("from_glib_full({call})"),
+            _ => format!("from_glib({call})"),
+        };
+        writeln!(w, "\t\t\t{call}")?;
+    } else {
+        writeln!(
+            w,
+            "\t\t\tif let Some(f) = (*parent_class).{} {{",
+            vfunc.vfunc_name
+        )?;
+        writeln!(w, "\t\t\t\t{call}")?;
+        writeln!(w, "\t\t\t}}")?;
+    }
+    writeln!(w, "\t\t}}")?;
+    writeln!(w, "\t}}")
+}
+
+fn generate_trampoline(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::object::Info,
+    subclass: &Subclass,
+    vfunc: &analysis::virtual_methods::Info,
+    trampoline: &Trampoline,
+) -> Result<()> {
+    let version = Version::if_stricter_than(vfunc.version, analysis.version);
+    cfg_deprecated(w, env, None, vfunc.deprecated_version, false, 0)?;
+    cfg_condition(w, vfunc.cfg_condition.as_ref(), false, 0)?;
+    version_condition(w, env, None, version, false, 0)?;
+    writeln!(
+        w,
+        "unsafe extern \"C\" fn {}<T: {}>({}){} {{",
+        trampoline.name,
+        subclass.impl_trait_name,
+        trampoline_parameters(env, trampoline),
+        trampoline_returns(env, trampoline),
+    )?;
+    // `unsafe_op_in_unsafe_fn` warns by default since edition 2024
+    let unsafe_block = env.config.edition.is_2024();
+    let indent = if unsafe_block {
+        writeln!(w, "\tunsafe {{")?;
+        "\t\t"
+    } else {
+        "\t"
+    };
+    writeln!(
+        w,
+        "{}let instance = &*({} as *mut T::Instance);",
+        indent, trampoline.parameters.c_parameters[0].name
+    )?;
+    writeln!(w, "{indent}let imp = instance.imp();")?;
+
+    let args = trampoline
+        .parameters
+        .rust_parameters
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter_map(|(ind, par)| {
+            trampoline.parameters.get(ind).map(|transformation| {
+                transformation.trampoline_from_glib(env, false, *par.nullable)
+            })
+        })
+        .collect::<Vec<_>>();
+    let ret = if trampoline.ret.typ == Default::default() {
+        String::new()
+    } else {
+        trampoline.ret.trampoline_to_glib(env)
+    };
+    writeln!(
+        w,
+        "{}imp.{}({}){}",
+        indent,
+        vfunc.name,
+        args.join(", "),
+        ret
+    )?;
+    if unsafe_block {
+        writeln!(w, "\t}}")?;
+    }
+    writeln!(w, "}}")
+}
+
+fn call_arguments(trampoline: &Trampoline) -> String {
+    trampoline
+        .parameters
+        .rust_parameters
+        .iter()
+        .skip(1)
+        .map(|par| par.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,10 +1,16 @@
 use std::{collections::HashMap, fs::File, io::prelude::*};
 
 use log::info;
-use toml::{self, value::Table, Value};
+use toml_edit::{value, Array, DocumentMut, Item, Table, TableLike, Value};
 
 use super::collect_versions;
-use crate::{config::Config, env::Env, file_saver::save_to_file, nameutil, version::Version};
+use crate::{
+    config::{external_libraries::ExternalLibrary, Config},
+    env::Env,
+    file_saver::save_to_file,
+    nameutil,
+    version::Version,
+};
 
 pub fn generate(env: &Env) -> String {
     info!("Generating sys Cargo.toml for {}", env.config.library_name);
@@ -16,29 +22,31 @@ pub fn generate(env: &Env) -> String {
         file.read_to_string(&mut toml_str).unwrap();
     }
     let empty = toml_str.trim().is_empty();
-    let mut root_table = toml::from_str(&toml_str).unwrap_or_else(|_| Table::new());
-    let crate_name = get_crate_name(&env.config, &root_table);
+    // Edited in place to keep what isn't generated untouched, comments and
+    // formatting included
+    let mut doc = toml_str
+        .parse::<DocumentMut>()
+        .unwrap_or_else(|_| DocumentMut::new());
+    let crate_name = get_crate_name(&env.config, &doc);
 
     if empty {
-        fill_empty(&mut root_table, env, &crate_name);
+        fill_empty(doc.as_table_mut(), env, &crate_name);
     }
-    fill_in(&mut root_table, env);
+    fill_in(doc.as_table_mut(), env);
 
-    save_to_file(&path, env.config.make_backup, |w| {
-        w.write_all(toml::to_string(&root_table).unwrap().as_bytes())
-    });
+    save_to_file(env, &path, |w| w.write_all(doc.to_string().as_bytes()));
 
     crate_name
 }
 
-fn fill_empty(root: &mut Table, env: &Env, crate_name: &str) {
+fn fill_empty(root: &mut dyn TableLike, env: &Env, crate_name: &str) {
     let package_name = nameutil::exported_crate_name(crate_name);
 
     {
         let package = upsert_table(root, "package");
         set_string(package, "name", package_name);
         set_string(package, "version", "0.0.1");
-        set_string(package, "edition", "2021");
+        set_string(package, "edition", env.config.edition.as_str());
     }
 
     {
@@ -47,14 +55,14 @@ fn fill_empty(root: &mut Table, env: &Env, crate_name: &str) {
     }
 
     let deps = upsert_table(root, "dependencies");
-    for ext_lib in &env.config.external_libraries {
-        let ext_package = if ext_lib.crate_name == "cairo" {
-            format!("{}-sys-rs", ext_lib.crate_name)
-        } else if ext_lib.crate_name == "gdk_pixbuf" {
-            "gdk-pixbuf-sys".into()
-        } else {
-            format!("{}-sys", ext_lib.crate_name)
-        };
+    // Configured dependencies are written by `fill_in`
+    for ext_lib in env
+        .config
+        .external_libraries
+        .iter()
+        .filter(|ext_lib| ext_lib.dependency.is_none())
+    {
+        let ext_package = sys_package_name(ext_lib);
         let repo_url = match ext_package.as_str() {
             "cairo-sys-rs" | "gdk-pixbuf-sys" | "gio-sys" | "gobject-sys" | "glib-sys"
             | "graphene-sys" | "pango-sys" | "pangocairo-sys" => {
@@ -91,28 +99,53 @@ fn fill_empty(root: &mut Table, env: &Env, crate_name: &str) {
             | "gstreamer-allocators-sys" => "https://gitlab.freedesktop.org/gstreamer/gstreamer-rs",
             &_ => "ADD GIT REPOSITORY URL HERE",
         };
-        let dep = upsert_table(deps, ext_package);
+        let dep = upsert_table(deps, &ext_package);
         set_string(dep, "git", repo_url);
     }
 }
 
-fn fill_in(root: &mut Table, env: &Env) {
+fn fill_in(root: &mut dyn TableLike, env: &Env) {
+    // The library is opened at runtime instead of being linked by the build
+    // script
+    let dynamic_loading = env.config.dynamic_loading;
+
     {
         let package = upsert_table(root, "package");
-        package
-            .entry("build")
-            .or_insert_with(|| Value::String("build.rs".into()));
+        if dynamic_loading {
+            unset(package, "build");
+        } else {
+            package.entry("build").or_insert(value("build.rs"));
+        }
         // set_string(package, "version", "0.2.0");
     }
 
     {
         let deps = upsert_table(root, "dependencies");
         set_string(deps, "libc", "0.2");
+        for ext_lib in &env.config.external_libraries {
+            if let Some(ref dependency) = ext_lib.dependency {
+                let dependency = dependency
+                    .to_toml()
+                    .to_string()
+                    .parse()
+                    .expect("Invalid dependency");
+                set_value(deps, &sys_package_name(ext_lib), dependency);
+            }
+        }
+        if dynamic_loading {
+            set_string(deps, "libloading", "0.8");
+        } else {
+            unset(deps, "libloading");
+        }
     }
 
     {
         let build_deps = upsert_table(root, "build-dependencies");
-        set_string(build_deps, "system-deps", "7");
+        if dynamic_loading {
+            unset(build_deps, "system-deps");
+        } else {
+            set_string(build_deps, "system-deps", "7");
+        }
     }
 
     {
@@ -126,29 +159,32 @@ fn fill_in(root: &mut Table, env: &Env) {
         let features = upsert_table(root, "features");
         let versions = collect_versions(env);
         versions.keys().fold(None::<Version>, |prev, &version| {
-            let prev_array: Vec<Value> =
+            let prev_array: Array =
                 get_feature_dependencies(version, prev, &env.config.feature_dependencies)
-                    .iter()
-                    .map(|s| Value::String(s.clone()))
+                    .into_iter()
                     .collect();
-            features.insert(version.to_feature(), Value::Array(prev_array));
+            set_value(features, &version.to_feature(), Value::Array(prev_array));
             Some(version)
         });
     }
 
-    {
+    if dynamic_loading {
+        // No build script is left to read it
+        let meta = upsert_table(root, "package");
+        let meta = upsert_table(meta, "metadata");
+        unset(meta, "system-deps");
+    } else {
         let meta = upsert_table(root, "package");
         let meta = upsert_table(meta, "metadata");
         let meta = upsert_table(meta, "system-deps");
 
         let ns = env.namespaces.main();
         if let Some(lib_name) = ns.package_names.first() {
-            let meta = upsert_table(meta, nameutil::lib_name_to_toml(lib_name));
+            let meta = upsert_table(meta, &nameutil::lib_name_to_toml(lib_name));
             // Allow both the name and version of a system dep to be overridden by hand
-            meta.entry("name")
-                .or_insert_with(|| Value::String(lib_name.clone()));
+            meta.entry("name").or_insert(value(lib_name));
             meta.entry("version")
-                .or_insert_with(|| Value::String(env.config.min_cfg_version.to_string()));
+                .or_insert(value(env.config.min_cfg_version.to_string()));
 
             // Old version API
             unset(meta, "feature-versions");
@@ -157,45 +193,52 @@ fn fill_in(root: &mut Table, env: &Env) {
                 .iter()
                 .filter(|(&v, _)| v > env.config.min_cfg_version)
                 .for_each(|(v, lib_version)| {
-                    let version_section = upsert_table(meta, v.to_feature());
+                    let version_section = upsert_table(meta, &v.to_feature());
                     // Allow system-deps version for this feature level to be overridden by hand
                     version_section
                         .entry("version")
-                        .or_insert_with(|| Value::String(lib_version.to_string()));
+                        .or_insert(value(lib_version.to_string()));
                 });
         }
     }
 
     {
-        // Small trick to prevent having double quotes around it since toml doesn't like
-        // having '.'
+        // Nested tables to prevent having double quotes around `docs.rs`
         let docs_rs_metadata = upsert_table(root, "package");
         let docs_rs_metadata = upsert_table(docs_rs_metadata, "metadata");
         let docs_rs_metadata = upsert_table(docs_rs_metadata, "docs");
         let docs_rs_metadata = upsert_table(docs_rs_metadata, "rs");
 
         // Set the rustc and rustdoc args to be able to build the docs on docs.rs without the libraries
-        docs_rs_metadata.entry("rustc-args").or_insert_with(|| {
-            Value::Array(vec![
-                Value::String("--cfg".to_string()),
-                Value::String("docsrs".to_string()),
-            ])
-        });
-        docs_rs_metadata.entry("rustdoc-args").or_insert_with(|| {
-            Value::Array(vec![
-                Value::String("--cfg".to_string()),
-                Value::String("docsrs".to_string()),
-                Value::String("--generate-link-to-definition".to_string()),
-            ])
-        });
+        docs_rs_metadata
+            .entry("rustc-args")
+            .or_insert(value(Array::from_iter(["--cfg", "docsrs"])));
+        docs_rs_metadata
+            .entry("rustdoc-args")
+            .or_insert(value(Array::from_iter([
+                "--cfg",
+                "docsrs",
+                "--generate-link-to-definition",
+            ])));
 
         // Generate docs for all features unless a list of features to be activated on docs.rs was specified
-        if let toml::map::Entry::Vacant(_) = docs_rs_metadata.entry("features") {
-            docs_rs_metadata.insert("all-features".to_string(), Value::Boolean(true));
+        if !docs_rs_metadata.contains_key("features") {
+            set_value(docs_rs_metadata, "all-features", Value::from(true));
         }
     }
 }
 
+/// Name of the `-sys` crate of an external library, as a dependency.
+fn sys_package_name(ext_lib: &ExternalLibrary) -> String {
+    if ext_lib.crate_name == "cairo" {
+        format!("{}-sys-rs", ext_lib.crate_name)
+    } else if ext_lib.crate_name == "gdk_pixbuf" {
+        "gdk-pixbuf-sys".into()
+    } else {
+        format!("{}-sys", ext_lib.crate_name)
+    }
+}
+
 fn get_feature_dependencies(
     version: Version,
     prev_version: Option<Version>,
@@ -212,36 +255,173 @@ fn get_feature_dependencies(
 }
 
 /// Returns the name of crate being currently generated.
-fn get_crate_name(config: &Config, root: &Table) -> String {
-    if let Some(Value::Table(lib)) = root.get("lib") {
-        if let Some(Value::String(lib_name)) = lib.get("name") {
-            // Converting don't needed as library target names cannot contain hyphens
-            return lib_name.clone();
-        }
+fn get_crate_name(config: &Config, root: &DocumentMut) -> String {
+    if let Some(lib_name) = root
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .and_then(Item::as_str)
+    {
+        // Converting don't needed as library target names cannot contain hyphens
+        return lib_name.to_owned();
     }
-    if let Some(Value::Table(package)) = root.get("package") {
-        if let Some(Value::String(package_name)) = package.get("name") {
-            return nameutil::crate_name(package_name);
-        }
+    if let Some(package_name) = root
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+    {
+        return nameutil::crate_name(package_name);
     }
     format!("{}_sys", nameutil::crate_name(&config.library_name))
 }
 
-fn set_string<S: Into<String>>(table: &mut Table, name: &str, new_value: S) {
-    table.insert(name.into(), Value::String(new_value.into()));
+fn set_string<S: Into<String>>(table: &mut dyn TableLike, name: &str, new_value: S) {
+    set_value(table, name, Value::from(new_value.into()));
 }
 
-fn unset(table: &mut Table, name: &str) {
+/// Sets a value, leaving it untouched if it's already the same, or keeping
+/// the comments around it otherwise.
+fn set_value(table: &mut dyn TableLike, name: &str, mut new_value: Value) {
+    match table.get_mut(name) {
+        Some(Item::Value(old_value)) => {
+            if !is_same_value(old_value, &new_value) {
+                *new_value.decor_mut() = old_value.decor().clone();
+                *old_value = new_value;
+            }
+        }
+        // Inline tables may have been written as standard ones, their keys
+        // are updated one by one
+        Some(Item::Table(old_table)) if new_value.is_inline_table() => {
+            let Value::InlineTable(new_table) = new_value else {
+                unreachable!()
+            };
+            let old_keys: Vec<String> = old_table.iter().map(|(k, _)| k.to_owned()).collect();
+            for key in old_keys {
+                if !new_table.contains_key(&key) {
+                    unset(old_table, &key);
+                }
+            }
+            for (key, mut value) in new_table {
+                value.decor_mut().clear();
+                set_value(old_table, &key, value);
+            }
+        }
+        _ => {
+            table.insert(name, Item::Value(new_value));
+        }
+    }
+}
+
+/// Compares values regardless of their formatting.
+fn is_same_value(a: &Value, b: &Value) -> bool {
+    fn parse(value: &Value) -> Option<toml::Value> {
+        let mut value = value.clone();
+        value.decor_mut().clear();
+        toml::from_str::<toml::Table>(&format!("value = {value}"))
+            .ok()?
+            .remove("value")
+    }
+    parse(a).is_some_and(|a| Some(a) == parse(b))
+}
+
+fn unset(table: &mut dyn TableLike, name: &str) {
     table.remove(name);
 }
 
-fn upsert_table<S: Into<String>>(parent: &mut Table, name: S) -> &mut Table {
-    if let Value::Table(table) = parent
-        .entry(name.into())
-        .or_insert_with(|| Value::Table(toml::map::Map::new()))
-    {
-        table
-    } else {
-        unreachable!()
+fn upsert_table<'a>(parent: &'a mut dyn TableLike, name: &str) -> &'a mut dyn TableLike {
+    parent
+        .entry(name)
+        .or_insert_with(|| {
+            // Only printed if it has values of its own
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_like_mut()
+        .unwrap_or_else(|| panic!("`{name}` is not a table"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(input: &str, f: impl FnOnce(&mut dyn TableLike)) -> String {
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        f(doc.as_table_mut());
+        doc.to_string()
+    }
+
+    #[test]
+    fn set_value_keeps_formatting() {
+        let input = r#"# Header
+[features]
+v1_2 = [ ]  # first
+v1_4 = [ "v1_2" ]  # second
+extra = ["v1_4"]
+"#;
+        let output = edit(input, |root| {
+            let features = upsert_table(root, "features");
+            set_value(features, "v1_2", Value::Array(Array::new()));
+            set_value(features, "v1_4", Value::Array(Array::from_iter(["v1_2"])));
+        });
+        assert_eq!(output, input);
+
+        let output = edit(input, |root| {
+            let features = upsert_table(root, "features");
+            set_value(features, "v1_4", Value::Array(Array::from_iter(["extra"])));
+            set_string(features, "v1_6", "new");
+        });
+        assert_eq!(
+            output,
+            r#"# Header
+[features]
+v1_2 = [ ]  # first
+v1_4 = ["extra"]  # second
+extra = ["v1_4"]
+v1_6 = "new"
+"#
+        );
+    }
+
+    #[test]
+    fn set_value_of_standard_table() {
+        let input = r#"[dependencies.glib-sys]
+# Comment
+version = "0.20"
+"#;
+        let output = edit(input, |root| {
+            let deps = upsert_table(root, "dependencies");
+            set_value(deps, "glib-sys", "{ version = \"0.20\" }".parse().unwrap());
+        });
+        assert_eq!(output, input);
+
+        let output = edit(input, |root| {
+            let deps = upsert_table(root, "dependencies");
+            set_value(deps, "glib-sys", "{ version = \"0.21\" }".parse().unwrap());
+        });
+        assert_eq!(
+            output,
+            r#"[dependencies.glib-sys]
+# Comment
+version = "0.21"
+"#
+        );
+
+        let output = edit(input, |root| {
+            let deps = upsert_table(root, "dependencies");
+            set_value(
+                deps,
+                "glib-sys",
+                "{ path = \"../glib/sys\", features = [\"v2_80\"] }"
+                    .parse()
+                    .unwrap(),
+            );
+        });
+        assert_eq!(
+            output,
+            r#"[dependencies.glib-sys]
+path = "../glib/sys"
+features = ["v2_80"]
+"#
+        );
     }
 }
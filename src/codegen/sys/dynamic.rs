@@ -0,0 +1,186 @@
+use std::{
+    collections::BTreeMap,
+    io::{Result, Write},
+};
+
+use super::functions::{generate_section_header, Section, Symbol};
+use crate::{
+    codegen::general::{
+        cfg_condition, cfg_condition_no_doc, version_condition, version_condition_no_doc,
+    },
+    env::Env,
+    version::Version,
+};
+
+/// Writes a table of function pointers resolved when the shared libraries are
+/// opened, instead of an `extern "C"` block linked at build time.
+pub fn generate(w: &mut dyn Write, env: &Env, sections: &[Section]) -> Result<()> {
+    let shared_libs = &env.namespaces.main().shared_libs;
+    writeln!(
+        w,
+        "/// Names of the shared libraries opened by [`Library::load`]."
+    )?;
+    writeln!(
+        w,
+        "pub const SHARED_LIBRARIES: &[&str] = &[{}];",
+        shared_libs
+            .iter()
+            .map(|lib| format!("{lib:?}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )?;
+    writeln!(w)?;
+
+    generate_struct(w, env, sections)?;
+    generate_impl(w, env, sections)?;
+    generate_resolve(w)
+}
+
+/// Functions added after the minimum supported version may be missing from
+/// the library found at runtime.
+fn is_optional(env: &Env, symbol: &Symbol) -> bool {
+    symbol
+        .version
+        .is_some_and(|v| v > env.config.min_cfg_version)
+}
+
+fn generate_struct(w: &mut dyn Write, env: &Env, sections: &[Section]) -> Result<()> {
+    writeln!(
+        w,
+        "/// Functions of the shared libraries, resolved at runtime."
+    )?;
+    writeln!(w, "pub struct Library {{")?;
+    for section in sections {
+        generate_section_header(w, section)?;
+        for symbol in &section.symbols {
+            let comment = if symbol.commented { "//" } else { "" };
+            version_condition(w, env, None, symbol.version, symbol.commented, 1)?;
+            cfg_condition(w, symbol.cfg_condition.as_ref(), symbol.commented, 1)?;
+            let fn_type = format!("unsafe extern \"C\" fn{}", symbol.signature);
+            if is_optional(env, symbol) {
+                writeln!(
+                    w,
+                    "    {}pub {}: Option<{}>,",
+                    comment, symbol.name, fn_type
+                )?;
+            } else {
+                writeln!(w, "    {}pub {}: {},", comment, symbol.name, fn_type)?;
+            }
+        }
+    }
+    writeln!(w)?;
+    writeln!(w, "    _libraries: Vec<libloading::Library>,")?;
+    writeln!(w, "}}")?;
+    writeln!(w)
+}
+
+fn generate_impl(w: &mut dyn Write, env: &Env, sections: &[Section]) -> Result<()> {
+    // The `unsafe` blocks in `unsafe fn` are required by edition 2024 and
+    // accepted by the earlier ones
+    w.write_all(
+        br#"impl Library {
+    /// Opens the [`SHARED_LIBRARIES`] and resolves the functions.
+    ///
+    /// Fails if a function of the minimum supported version is missing, the
+    /// ones added later are checked by the `is_v*_available()` methods.
+    ///
+    /// # Safety
+    ///
+    /// The libraries' initialization routines are run, and the functions
+    /// must have the signatures declared here.
+    pub unsafe fn load() -> Result<Self, libloading::Error> {
+        let mut libraries = Vec::with_capacity(SHARED_LIBRARIES.len());
+        for name in SHARED_LIBRARIES {
+            libraries.push(unsafe { libloading::Library::new(name)? });
+        }
+        unsafe { Self::from_libraries(libraries) }
+    }
+
+    /// Resolves the functions from already opened libraries, searched in
+    /// order.
+    ///
+    /// # Safety
+    ///
+    /// The functions must have the signatures declared here.
+    pub unsafe fn from_libraries(
+        libraries: Vec<libloading::Library>,
+    ) -> Result<Self, libloading::Error> {
+        unsafe {
+            Ok(Self {
+"#,
+    )?;
+
+    let mut versions = BTreeMap::<Version, Vec<&str>>::new();
+    for section in sections {
+        for symbol in &section.symbols {
+            let comment = if symbol.commented { "//" } else { "" };
+            version_condition_no_doc(w, env, None, symbol.version, symbol.commented, 4)?;
+            cfg_condition_no_doc(w, symbol.cfg_condition.as_ref(), symbol.commented, 4)?;
+            let optional = is_optional(env, symbol);
+            writeln!(
+                w,
+                "                {}{}: resolve(&libraries, b\"{}\\0\"){},",
+                comment,
+                symbol.name,
+                symbol.name,
+                if optional { ".ok()" } else { "?" }
+            )?;
+            // Checking the ones with a custom condition would require to
+            // repeat it in the availability check
+            if optional && !symbol.commented && symbol.cfg_condition.is_none() {
+                versions
+                    .entry(symbol.version.unwrap())
+                    .or_default()
+                    .push(&symbol.name);
+            }
+        }
+    }
+    writeln!(w, "                _libraries: libraries,")?;
+    writeln!(w, "            }})")?;
+    writeln!(w, "        }}")?;
+    writeln!(w, "    }}")?;
+
+    let mut prev_version = None;
+    for (version, names) in &versions {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "    /// Whether the functions added up to version {version} were found."
+        )?;
+        version_condition(w, env, None, Some(*version), false, 1)?;
+        writeln!(
+            w,
+            "    pub fn is_{}_available(&self) -> bool {{",
+            version.to_feature()
+        )?;
+        let checks = prev_version
+            .map(|v: Version| format!("self.is_{}_available()", v.to_feature()))
+            .into_iter()
+            .chain(names.iter().map(|name| format!("self.{name}.is_some()")))
+            .collect::<Vec<_>>();
+        writeln!(w, "        {}", checks.join("\n            && "))?;
+        writeln!(w, "    }}")?;
+        prev_version = Some(*version);
+    }
+    writeln!(w, "}}")
+}
+
+fn generate_resolve(w: &mut dyn Write) -> Result<()> {
+    w.write_all(
+        br#"
+unsafe fn resolve<T: Copy>(
+    libraries: &[libloading::Library],
+    name: &[u8],
+) -> Result<T, libloading::Error> {
+    let mut error = None;
+    for library in libraries {
+        match unsafe { library.get::<T>(name) } {
+            Ok(symbol) => return Ok(*symbol),
+            Err(e) => error = Some(e),
+        }
+    }
+    Err(error.expect("No shared library to resolve the functions from"))
+}
+"#,
+    )
+}
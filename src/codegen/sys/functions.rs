@@ -10,6 +10,7 @@ use crate::{
     env::Env,
     library, nameutil,
     traits::*,
+    version::Version,
 };
 
 // used as glib:get-type in GLib-2.0.gir
@@ -20,198 +21,221 @@ fn default_obj() -> &'static GObject {
     OBJ.get_or_init(Default::default)
 }
 
-pub fn generate_records_funcs(
-    w: &mut dyn Write,
+/// Functions declared for a type, or the global functions of the namespace.
+pub struct Section<'a> {
+    pub c_type: String,
+    pub symbols: Vec<Symbol<'a>>,
+}
+
+/// A function exported by the shared library.
+pub struct Symbol<'a> {
+    pub name: String,
+    /// `None` for the `get_type` function.
+    pub function: Option<&'a library::Function>,
+    /// Parameters and return type, as written after the function name.
+    pub signature: String,
+    pub commented: bool,
+    pub version: Option<Version>,
+    pub cfg_condition: Option<String>,
+}
+
+/// Collects the functions of the namespace, in the order they're declared in
+/// the `-sys` crate.
+pub fn collect<'a>(
     env: &Env,
-    records: &[&library::Record],
-) -> Result<()> {
+    enums: &[&'a library::Enumeration],
+    bitfields: &[&'a library::Bitfield],
+    unions: &[&'a library::Union],
+    records: &[&'a library::Record],
+    classes: &[&'a library::Class],
+    interfaces: &[&'a library::Interface],
+    functions: &'a [library::Function],
+) -> Vec<Section<'a>> {
+    let mut sections = Vec::new();
+    collect_enums_funcs(&mut sections, env, enums);
+    collect_bitfields_funcs(&mut sections, env, bitfields);
+    collect_unions_funcs(&mut sections, env, unions);
+    collect_records_funcs(&mut sections, env, records);
+    collect_classes_funcs(&mut sections, env, classes);
+    collect_interfaces_funcs(&mut sections, env, interfaces);
+    collect_other_funcs(&mut sections, env, functions);
+    sections
+}
+
+fn collect_records_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
+    env: &Env,
+    records: &[&'a library::Record],
+) {
     let intern_str = INTERN.to_string();
     for record in records {
         let name = format!("{}.{}", env.config.library_name, record.name);
         let obj = env.config.objects.get(&name).unwrap_or(default_obj());
         let version = obj.version.or(record.version);
         let glib_get_type = record.glib_get_type.as_ref().unwrap_or(&intern_str);
-        generate_object_funcs(
-            w,
+        collect_object_funcs(
+            sections,
             env,
             obj,
             version,
             &record.c_type,
             glib_get_type,
             &record.functions,
-        )?;
+        );
     }
-
-    Ok(())
 }
 
-pub fn generate_classes_funcs(
-    w: &mut dyn Write,
+fn collect_classes_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
-    classes: &[&library::Class],
-) -> Result<()> {
+    classes: &[&'a library::Class],
+) {
     for klass in classes {
         let name = format!("{}.{}", env.config.library_name, klass.name);
         let obj = env.config.objects.get(&name).unwrap_or(default_obj());
         let version = obj.version.or(klass.version);
-        generate_object_funcs(
-            w,
+        collect_object_funcs(
+            sections,
             env,
             obj,
             version,
             &klass.c_type,
             &klass.glib_get_type,
             &klass.functions,
-        )?;
+        );
     }
-
-    Ok(())
 }
 
-pub fn generate_bitfields_funcs(
-    w: &mut dyn Write,
+fn collect_bitfields_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
-    bitfields: &[&library::Bitfield],
-) -> Result<()> {
+    bitfields: &[&'a library::Bitfield],
+) {
     let intern_str = INTERN.to_string();
     for bitfield in bitfields {
         let name = format!("{}.{}", env.config.library_name, bitfield.name);
         let obj = env.config.objects.get(&name).unwrap_or(default_obj());
         let version = obj.version.or(bitfield.version);
         let glib_get_type = bitfield.glib_get_type.as_ref().unwrap_or(&intern_str);
-        generate_object_funcs(
-            w,
+        collect_object_funcs(
+            sections,
             env,
             obj,
             version,
             &bitfield.c_type,
             glib_get_type,
             &bitfield.functions,
-        )?;
+        );
     }
-
-    Ok(())
 }
 
-pub fn generate_enums_funcs(
-    w: &mut dyn Write,
+fn collect_enums_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
-    enums: &[&library::Enumeration],
-) -> Result<()> {
+    enums: &[&'a library::Enumeration],
+) {
     let intern_str = INTERN.to_string();
     for en in enums {
         let name = format!("{}.{}", env.config.library_name, en.name);
         let obj = env.config.objects.get(&name).unwrap_or(default_obj());
         let version = obj.version.or(en.version);
         let glib_get_type = en.glib_get_type.as_ref().unwrap_or(&intern_str);
-        generate_object_funcs(
-            w,
+        collect_object_funcs(
+            sections,
             env,
             obj,
             version,
             &en.c_type,
             glib_get_type,
             &en.functions,
-        )?;
+        );
     }
-
-    Ok(())
 }
 
-pub fn generate_unions_funcs(
-    w: &mut dyn Write,
+fn collect_unions_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
-    unions: &[&library::Union],
-) -> Result<()> {
+    unions: &[&'a library::Union],
+) {
     let intern_str = INTERN.to_string();
     for union in unions {
         let Some(ref c_type) = union.c_type else {
-            return Ok(());
+            return;
         };
         let name = format!("{}.{}", env.config.library_name, union.name);
         let obj = env.config.objects.get(&name).unwrap_or(default_obj());
         let glib_get_type = union.glib_get_type.as_ref().unwrap_or(&intern_str);
-        generate_object_funcs(
-            w,
+        collect_object_funcs(
+            sections,
             env,
             obj,
             obj.version,
             c_type,
             glib_get_type,
             &union.functions,
-        )?;
+        );
     }
-
-    Ok(())
 }
 
-pub fn generate_interfaces_funcs(
-    w: &mut dyn Write,
+fn collect_interfaces_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
-    interfaces: &[&library::Interface],
-) -> Result<()> {
+    interfaces: &[&'a library::Interface],
+) {
     for interface in interfaces {
         let name = format!("{}.{}", env.config.library_name, interface.name);
         let obj = env.config.objects.get(&name).unwrap_or(default_obj());
         let version = obj.version.or(interface.version);
-        generate_object_funcs(
-            w,
+        collect_object_funcs(
+            sections,
             env,
             obj,
             version,
             &interface.c_type,
             &interface.glib_get_type,
             &interface.functions,
-        )?;
+        );
     }
-
-    Ok(())
 }
 
-pub fn generate_other_funcs(
-    w: &mut dyn Write,
+fn collect_other_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
-    functions: &[library::Function],
-) -> Result<()> {
+    functions: &'a [library::Function],
+) {
     let name = format!("{}.*", env.config.library_name);
     let obj = env.config.objects.get(&name).unwrap_or(default_obj());
-    generate_object_funcs(w, env, obj, None, "Other functions", INTERN, functions)
+    collect_object_funcs(
+        sections,
+        env,
+        obj,
+        None,
+        "Other functions",
+        INTERN,
+        functions,
+    );
 }
 
-fn generate_cfg_configure(
-    w: &mut dyn Write,
-    configured_functions: &[&Function],
-    commented: bool,
-) -> Result<()> {
-    let cfg_condition_ = configured_functions
+fn configured_cfg_condition(configured_functions: &[&Function]) -> Option<String> {
+    configured_functions
         .iter()
-        .find_map(|f| f.cfg_condition.as_ref());
-    cfg_condition(w, cfg_condition_, commented, 1)?;
-    Ok(())
+        .find_map(|f| f.cfg_condition.clone())
 }
 
-fn generate_object_funcs(
-    w: &mut dyn Write,
+fn collect_object_funcs<'a>(
+    sections: &mut Vec<Section<'a>>,
     env: &Env,
     obj: &GObject,
-    version: Option<crate::version::Version>,
+    version: Option<Version>,
     c_type: &str,
     glib_get_type: &str,
-    functions: &[library::Function],
-) -> Result<()> {
+    functions: &'a [library::Function],
+) {
     let write_get_type = glib_get_type != INTERN;
-    if write_get_type || !functions.is_empty() {
-        writeln!(w)?;
-        writeln!(
-            w,
-            "    //========================================================================="
-        )?;
-        writeln!(w, "    // {c_type}")?;
-        writeln!(
-            w,
-            "    //========================================================================="
-        )?;
+    if !write_get_type && functions.is_empty() {
+        return;
     }
+    let mut symbols = Vec::new();
     if write_get_type {
         let configured_functions = obj.functions.matched("get_type");
 
@@ -223,9 +247,14 @@ fn generate_object_funcs(
                 .chain(configured_functions.iter().map(|f| f.version))
                 .max()
                 .flatten();
-            version_condition(w, env, None, version, false, 1)?;
-            generate_cfg_configure(w, &configured_functions, false)?;
-            writeln!(w, "    pub fn {glib_get_type}() -> GType;")?;
+            symbols.push(Symbol {
+                name: glib_get_type.to_owned(),
+                function: None,
+                signature: "() -> GType".to_owned(),
+                commented: false,
+                version,
+                cfg_condition: configured_cfg_condition(&configured_functions),
+            });
         }
     }
 
@@ -238,8 +267,7 @@ fn generate_object_funcs(
             continue;
         }
 
-        let (commented, sig) = function_signature(env, func, false);
-        let comment = if commented { "//" } else { "" };
+        let (commented, signature) = function_signature(env, func, false);
 
         // If a version was configured for this function specifically then use that,
         // otherwise use the (fixed up!) version of the function, if any, otherwise
@@ -252,13 +280,64 @@ fn generate_object_funcs(
             .or(func.version)
             .or(version);
 
-        version_condition(w, env, None, version, commented, 1)?;
-        let name = func.c_identifier.as_ref().unwrap();
-        generate_cfg_configure(w, &configured_functions, commented)?;
-        writeln!(w, "    {comment}pub fn {name}{sig};")?;
+        symbols.push(Symbol {
+            name: func.c_identifier.clone().unwrap(),
+            function: Some(func),
+            signature,
+            commented,
+            version,
+            cfg_condition: configured_cfg_condition(&configured_functions),
+        });
     }
 
-    Ok(())
+    sections.push(Section {
+        c_type: c_type.to_owned(),
+        symbols,
+    });
+}
+
+/// Writes the header comment of a section, used in both linking modes.
+pub fn generate_section_header(w: &mut dyn Write, section: &Section) -> Result<()> {
+    writeln!(w)?;
+    writeln!(
+        w,
+        "    //========================================================================="
+    )?;
+    writeln!(w, "    // {}", section.c_type)?;
+    writeln!(
+        w,
+        "    //========================================================================="
+    )
+}
+
+/// Declares the functions in an `extern "C"` block, linked at build time.
+pub fn generate_extern(w: &mut dyn Write, env: &Env, sections: &[Section]) -> Result<()> {
+    let edition_2024 = env.config.edition.is_2024();
+    if edition_2024 {
+        writeln!(w, "unsafe extern \"C\" {{")?;
+    } else {
+        writeln!(w, "extern \"C\" {{")?;
+    }
+    for section in sections {
+        generate_section_header(w, section)?;
+        for symbol in &section.symbols {
+            let comment = if symbol.commented { "//" } else { "" };
+            // Getting a type can't break any invariant
+            let safety = match (edition_2024, symbol.function) {
+                (false, _) => "",
+                (true, None) => "safe ",
+                (true, Some(_)) => "unsafe ",
+            };
+            version_condition(w, env, None, symbol.version, symbol.commented, 1)?;
+            cfg_condition(w, symbol.cfg_condition.as_ref(), symbol.commented, 1)?;
+            writeln!(
+                w,
+                "    {}pub {}fn {}{};",
+                comment, safety, symbol.name, symbol.signature
+            )?;
+        }
+    }
+    writeln!(w, "\n}}")
 }
 
 pub fn generate_callbacks(
@@ -325,7 +404,7 @@ fn function_parameter(env: &Env, par: &library::Parameter, bare: bool) -> (bool,
     } else {
         format!(
             "{}: {}",
-            nameutil::mangle_keywords(&*par.name),
+            nameutil::mangle_keywords(&*par.name, env.config.edition),
             ffi_type.into_string()
         )
     };
@@ -5,7 +5,7 @@ use std::{
 
 use log::info;
 
-use super::{ffi_type::ffi_type, fields, functions, statics};
+use super::{dynamic, ffi_type::ffi_type, fields, functions, statics};
 use crate::{
     codegen::general::{self, cfg_condition, version_condition},
     config::constants,
@@ -22,7 +22,7 @@ pub fn generate(env: &Env) {
     let path = env.config.auto_path.join(file_name_sys("lib"));
 
     info!("Generating file {:?}", path);
-    save_to_file(&path, env.config.make_backup, |w| generate_lib(w, env));
+    save_to_file(env, &path, |w| generate_lib(w, env));
 }
 
 fn generate_lib(w: &mut dyn Write, env: &Env) -> Result<()> {
@@ -77,16 +77,21 @@ fn generate_lib(w: &mut dyn Write, env: &Env) -> Result<()> {
     }
 
     if !env.namespaces.main().shared_libs.is_empty() {
-        writeln!(w, "extern \"C\" {{")?;
-        functions::generate_enums_funcs(w, env, &enums)?;
-        functions::generate_bitfields_funcs(w, env, &bitfields)?;
-        functions::generate_unions_funcs(w, env, &unions)?;
-        functions::generate_records_funcs(w, env, &records)?;
-        functions::generate_classes_funcs(w, env, &classes)?;
-        functions::generate_interfaces_funcs(w, env, &interfaces)?;
-        functions::generate_other_funcs(w, env, &ns.functions)?;
-
-        writeln!(w, "\n}}")?;
+        let sections = functions::collect(
+            env,
+            &enums,
+            &bitfields,
+            &unions,
+            &records,
+            &classes,
+            &interfaces,
+            &ns.functions,
+        );
+        if env.config.dynamic_loading {
+            dynamic::generate(w, env, &sections)?;
+        } else {
+            functions::generate_extern(w, env, &sections)?;
+        }
     }
 
     Ok(())
@@ -148,7 +153,7 @@ fn find_modules(env: &Env) -> Result<Vec<String>> {
     Ok(vec)
 }
 
-fn prepare<T: Ord>(ns: &Namespace) -> Vec<&T>
+pub(super) fn prepare<T: Ord>(ns: &Namespace) -> Vec<&T>
 where
     Type: MaybeRef<T>,
 {
@@ -4,6 +4,7 @@ use crate::{codegen::generate_single_version_file, env::Env, version::Version};
 
 mod build;
 mod cargo_toml;
+mod dynamic;
 pub mod ffi_type;
 mod fields;
 mod functions;
@@ -14,7 +15,10 @@ mod tests;
 pub fn generate(env: &Env) {
     generate_single_version_file(env);
     lib_::generate(env);
-    build::generate(env);
+    // Nothing is linked when the library is loaded at runtime
+    if !env.config.dynamic_loading {
+        build::generate(env);
+    }
     let crate_name = cargo_toml::generate(env);
     tests::generate(env, &crate_name);
 }
@@ -5,13 +5,23 @@ use std::{
 
 use log::info;
 
+use super::{
+    ffi_type::ffi_type,
+    fields,
+    functions::{self, Symbol},
+    lib_::prepare,
+    statics,
+};
 use crate::{
     analysis::types::IsIncomplete,
     codegen::general,
-    config::gobjects::GStatus,
+    config::{gobjects::GStatus, Edition},
     env::Env,
     file_saver::save_to_file,
     library::{self, Bitfield, Enumeration, Namespace, Type, MAIN_NAMESPACE},
+    nameutil,
+    traits::*,
+    version::Version,
 };
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -23,6 +33,17 @@ struct CType {
     cfg_condition: Option<String>,
 }
 
+#[derive(Debug)]
+struct CField {
+    /// Name of the structure or union in C.
+    parent: String,
+    /// Name of the field in the `-sys` crate.
+    name: String,
+    /// Name of the field in C.
+    c_name: String,
+    cfg_condition: Option<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 struct CConstant {
     /// Identifier in C.
@@ -32,11 +53,27 @@ struct CConstant {
     status: GStatus,
 }
 
+#[derive(Debug)]
+struct CFunction {
+    /// Identifier in C.
+    name: String,
+    /// Function pointer type declaring `prototype`, built from the C types of
+    /// the GIR.
+    prototype: String,
+    /// C types of the return value and the parameters, `void` included.
+    c_types: Vec<String>,
+    /// Rust types of the return value and the parameters in the `-sys` crate.
+    rust_types: Vec<String>,
+    version: Option<Version>,
+}
+
 pub fn generate(env: &Env, crate_name: &str) {
     let ctypes = prepare_ctypes(env);
+    let cfields = prepare_cfields(env);
     let cconsts = prepare_cconsts(env);
+    let cfunctions = prepare_cfunctions(env);
 
-    if ctypes.is_empty() && cconsts.is_empty() {
+    if ctypes.is_empty() && cconsts.is_empty() && cfunctions.is_empty() {
         return;
     }
 
@@ -44,24 +81,36 @@ pub fn generate(env: &Env, crate_name: &str) {
 
     let manual_h = tests.join("manual.h");
     if !manual_h.exists() {
-        save_to_file(&manual_h, env.config.make_backup, |w| {
-            generate_manual_h(env, &manual_h, w)
-        });
+        save_to_file(env, &manual_h, |w| generate_manual_h(env, &manual_h, w));
     }
 
     let layout_c = tests.join("layout.c");
-    save_to_file(&layout_c, env.config.make_backup, |w| {
-        generate_layout_c(env, &layout_c, w, &ctypes)
+    save_to_file(env, &layout_c, |w| {
+        generate_layout_c(env, &layout_c, w, &ctypes, &cfields)
     });
 
     let constant_c = tests.join("constant.c");
-    save_to_file(&constant_c, env.config.make_backup, |w| {
+    save_to_file(env, &constant_c, |w| {
         generate_constant_c(env, &constant_c, w, &cconsts)
     });
 
+    let function_c = tests.join("function.c");
+    save_to_file(env, &function_c, |w| {
+        generate_function_c(env, &function_c, w, &cfunctions)
+    });
+
     let abi_rs = tests.join("abi.rs");
-    save_to_file(&abi_rs, env.config.make_backup, |w| {
-        generate_abi_rs(env, &abi_rs, w, crate_name, &ctypes, &cconsts)
+    save_to_file(env, &abi_rs, |w| {
+        generate_abi_rs(
+            env,
+            &abi_rs,
+            w,
+            crate_name,
+            &ctypes,
+            &cfields,
+            &cconsts,
+            &cfunctions,
+        )
     });
 }
 
@@ -107,6 +156,65 @@ fn prepare_ctype(env: &Env, ns: &Namespace, t: &Type) -> Option<CType> {
     })
 }
 
+fn prepare_cfields(env: &Env) -> Vec<CField> {
+    let ns = env.library.namespace(MAIN_NAMESPACE);
+    let mut types: Vec<(fields::Fields, &[library::Field])> = ns
+        .types
+        .iter()
+        .filter_map(Option::as_ref)
+        .filter(|t| !t.is_incomplete(&env.library))
+        .filter(|t| {
+            let full_name = format!("{}.{}", ns.name, t.get_name());
+            !env.type_status_sys(&full_name).ignored()
+        })
+        .filter_map(|t| match t {
+            // GHookList is written by hand, and the others are opaque
+            Type::Record(
+                record @ library::Record {
+                    disguised: false,
+                    pointer: false,
+                    ..
+                },
+            ) if record.c_type != "GHookList" => {
+                Some((fields::from_record(env, record), &record.fields[..]))
+            }
+            Type::Class(class) => Some((fields::from_class(env, class), &class.fields[..])),
+            Type::Union(union) if union.c_type.is_some() => {
+                Some((fields::from_union(env, union), &union.fields[..]))
+            }
+            _ => None,
+        })
+        .filter(|(fields, _)| !fields.external && !is_name_made_up(&fields.name))
+        .collect();
+    types.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+
+    let mut cfields = Vec::new();
+    for (fields, library_fields) in &types {
+        // Fields after a truncation are not part of the Rust structure
+        for (field, library_field) in fields.fields.iter().zip(library_fields.iter()) {
+            if library_field.private || library_field.bits.is_some() {
+                continue;
+            }
+            cfields.push(CField {
+                parent: fields.name.clone(),
+                name: field.name.clone(),
+                c_name: c_field_name(&field.name, env.config.edition).to_owned(),
+                cfg_condition: fields.cfg_condition.clone(),
+            });
+        }
+    }
+    cfields
+}
+
+/// Name of the field in C, before it was mangled to not clash with a Rust
+/// keyword.
+fn c_field_name(name: &str, edition: Edition) -> &str {
+    match name.strip_suffix('_') {
+        Some(stripped) if nameutil::needs_mangling(stripped, edition) => stripped,
+        _ => name,
+    }
+}
+
 fn prepare_cconsts(env: &Env) -> Vec<CConstant> {
     let ns = env.library.namespace(MAIN_NAMESPACE);
     let mut constants: Vec<CConstant> = ns
@@ -187,6 +295,88 @@ fn prepare_cconsts(env: &Env) -> Vec<CConstant> {
     constants
 }
 
+fn prepare_cfunctions(env: &Env) -> Vec<CFunction> {
+    let ns = env.library.namespace(MAIN_NAMESPACE);
+    let sections = functions::collect(
+        env,
+        &prepare(ns),
+        &prepare(ns),
+        &prepare(ns),
+        &prepare(ns),
+        &prepare(ns),
+        &prepare(ns),
+        &ns.functions,
+    );
+
+    sections
+        .iter()
+        .flat_map(|section| &section.symbols)
+        // The C side has no equivalent of custom conditions
+        .filter(|symbol| !symbol.commented && symbol.cfg_condition.is_none())
+        .filter_map(|symbol| prepare_cfunction(env, symbol))
+        .collect()
+}
+
+fn prepare_cfunction(env: &Env, symbol: &Symbol<'_>) -> Option<CFunction> {
+    let Some(func) = symbol.function else {
+        return Some(CFunction {
+            name: symbol.name.clone(),
+            prototype: "GType (*prototype)(void)".to_owned(),
+            c_types: vec!["GType".to_owned()],
+            rust_types: vec!["GType".to_owned()],
+            version: symbol.version,
+        });
+    };
+
+    let mut c_types = Vec::with_capacity(func.parameters.len() + 1);
+    let mut rust_types = Vec::with_capacity(func.parameters.len() + 1);
+    let mut c_parameters = Vec::with_capacity(func.parameters.len());
+    if func.ret.typ == Default::default() {
+        c_types.push("void".to_owned());
+        rust_types.push("()".to_owned());
+    } else if func.ret.c_type.is_empty() {
+        return None;
+    } else {
+        c_types.push(func.ret.c_type.clone());
+        rust_types.push(
+            ffi_type(env, func.ret.typ, &func.ret.c_type)
+                .ok()?
+                .into_string(),
+        );
+    }
+    for par in &func.parameters {
+        if let Type::Basic(library::Basic::VarArgs) = env.library.type_(par.typ) {
+            c_parameters.push("...".to_owned());
+            continue;
+        }
+        if par.c_type.is_empty() {
+            return None;
+        }
+        c_parameters.push(par.c_type.clone());
+        c_types.push(par.c_type.clone());
+        rust_types.push(ffi_type(env, par.typ, &par.c_type).ok()?.into_string());
+    }
+    if c_parameters.is_empty() {
+        c_parameters.push("void".to_owned());
+    }
+
+    Some(CFunction {
+        name: symbol.name.clone(),
+        prototype: format!("{} (*prototype)({})", c_types[0], c_parameters.join(", ")),
+        c_types,
+        rust_types,
+        version: symbol.version,
+    })
+}
+
+/// Name of the C preprocessor macro defined when the function of `version`
+/// are checked.
+fn version_define(env: &Env, version: Option<Version>) -> Option<String> {
+    version
+        .filter(|&v| v > env.config.min_cfg_version)
+        .map(|v| format!("FEATURE_{}", v.to_feature().to_uppercase()))
+}
+
 /// Checks if type name is unlikely to correspond to a real C type name.
 fn is_name_made_up(name: &str) -> bool {
     // Unnamed types are assigned name during parsing, those names contain an
@@ -216,12 +406,14 @@ fn generate_layout_c(
     path: &Path,
     w: &mut dyn Write,
     ctypes: &[CType],
+    cfields: &[CField],
 ) -> io::Result<()> {
     info!("Generating file {:?}", path);
     general::start_comments(w, &env.config)?;
     writeln!(w)?;
     writeln!(w, "#include \"manual.h\"")?;
     writeln!(w, "#include <stdalign.h>")?;
+    writeln!(w, "#include <stddef.h>")?;
     writeln!(w, "#include <stdio.h>")?;
     writeln!(w)?;
     writeln!(w, "{}", r"int main() {")?;
@@ -233,6 +425,14 @@ fn generate_layout_c(
             ctype = ctype.name
         )?;
     }
+    for cfield in cfields {
+        writeln!(
+            w,
+            "    printf(\"%s;%zu\\n\", \"{parent}.{field}\", offsetof({parent}, {field}));",
+            parent = cfield.parent,
+            field = cfield.c_name
+        )?;
+    }
 
     writeln!(w, "    return 0;")?;
     writeln!(w, "{}", r"}")
@@ -291,6 +491,58 @@ fn generate_constant_c(
     writeln!(w, "{}", r"}")
 }
 
+#[allow(clippy::write_literal)]
+fn generate_function_c(
+    env: &Env,
+    path: &Path,
+    w: &mut dyn Write,
+    cfunctions: &[CFunction],
+) -> io::Result<()> {
+    info!("Generating file {:?}", path);
+    general::start_comments(w, &env.config)?;
+    writeln!(w)?;
+    writeln!(w, "#include \"manual.h\"")?;
+    writeln!(w, "#include <stdio.h>")?;
+    writeln!(w)?;
+    writeln!(w, "{}", r"int main() {")?;
+
+    for cfunction in cfunctions {
+        let define = version_define(env, cfunction.version);
+        if let Some(ref define) = define {
+            writeln!(w, "#ifdef {define}")?;
+        }
+        writeln!(w, "    {{")?;
+        writeln!(w, "        {};", cfunction.prototype)?;
+        // Not evaluated, so the library doesn't need to be linked
+        writeln!(w, "        (void) sizeof(prototype = {});", cfunction.name)?;
+        let sizes = cfunction
+            .c_types
+            .iter()
+            .map(|c_type| {
+                if c_type == "void" {
+                    ", (size_t) 0".to_owned()
+                } else {
+                    format!(", sizeof({c_type})")
+                }
+            })
+            .collect::<String>();
+        writeln!(
+            w,
+            "        printf(\"%s{}\\n\", \"{}\"{});",
+            ";%zu".repeat(cfunction.c_types.len()),
+            cfunction.name,
+            sizes
+        )?;
+        writeln!(w, "    }}")?;
+        if define.is_some() {
+            writeln!(w, "#endif")?;
+        }
+    }
+
+    writeln!(w, "    return 0;")?;
+    writeln!(w, "{}", r"}")
+}
+
 #[allow(clippy::write_literal)]
 fn generate_abi_rs(
     env: &Env,
@@ -298,7 +550,9 @@ fn generate_abi_rs(
     w: &mut dyn Write,
     crate_name: &str,
     ctypes: &[CType],
+    cfields: &[CField],
     cconsts: &[CConstant],
+    cfunctions: &[CFunction],
 ) -> io::Result<()> {
     let ns = env.library.namespace(MAIN_NAMESPACE);
     let mut package_names = ns.package_names.join("\", \"");
@@ -312,9 +566,30 @@ fn generate_abi_rs(
     writeln!(w, "#![cfg(unix)]")?;
     writeln!(w)?;
 
-    if !ctypes.is_empty() {
+    if !ctypes.is_empty() || !cfunctions.is_empty() {
         writeln!(w, "use {crate_name}::*;")?;
+    }
+    if !ctypes.is_empty() {
         writeln!(w, "use std::mem::{{align_of, size_of}};")?;
+    } else if !cfunctions.is_empty() {
+        writeln!(w, "use std::mem::size_of;")?;
+    }
+    if !cfunctions.is_empty() {
+        // Types used in the functions' signatures, as imported by the crate
+        for library in &env.config.external_libraries {
+            writeln!(w, "#[allow(unused_imports)]")?;
+            writeln!(
+                w,
+                "use {}_sys as {};",
+                library.crate_name.replace('-', "_"),
+                nameutil::crate_name(&library.namespace)
+            )?;
+        }
+        statics::after_extern_crates(w)?;
+        if env.config.library_name != "GLib" {
+            statics::use_glib(w)?;
+        }
+        writeln!(w)?;
     }
 
     writeln!(w, "use std::env;")?;
@@ -433,7 +708,7 @@ impl Results {
 fn cross_validate_constants_with_c() {
     let mut c_constants: Vec<(String, String)> = Vec::new();
 
-    for l in get_c_output("constant").unwrap().lines() {
+    for l in get_c_output("constant", &[]).unwrap().lines() {
         let (name, value) = l.split_once(';').expect("Missing ';' separator");
         c_constants.push((name.to_owned(), value.to_owned()));
     }
@@ -463,13 +738,57 @@ fn cross_validate_constants_with_c() {
     results.expect_total_success();
 }
 
+#[test]
+fn cross_validate_functions_with_c() {
+    let mut c_functions = Vec::new();
+
+    for l in get_c_output("function", FUNCTION_C_ARGS).unwrap().lines() {
+        let (name, sizes) = l.split_once(';').expect("Missing ';' separator");
+        let sizes = sizes
+            .split(';')
+            .map(|size| size.parse().expect("Failed to parse size"))
+            .collect::<Vec<usize>>();
+        c_functions.push((name.to_owned(), sizes));
+    }
+
+    let mut results = Results::default();
+
+    for ((rust_name, rust_sizes), (c_name, c_sizes)) in
+        RUST_FUNCTIONS.iter().zip(c_functions.iter())
+    {
+        if rust_name != c_name {
+            results.record_failed();
+            eprintln!("Name mismatch:\nRust: {rust_name:?}\nC:    {c_name:?}");
+            continue;
+        }
+
+        if rust_sizes != c_sizes {
+            results.record_failed();
+            eprintln!(
+                "Return value and parameters sizes mismatch for {rust_name}\nRust: {rust_sizes:?}\nC:    {c_sizes:?}",
+            );
+            continue;
+        }
+
+        results.record_passed();
+    }
+
+    results.expect_total_success();
+}
+
 #[test]
 fn cross_validate_layout_with_c() {
     let mut c_layouts = Vec::new();
+    let mut c_offsets = Vec::new();
 
-    for l in get_c_output("layout").unwrap().lines() {
+    for l in get_c_output("layout", &[]).unwrap().lines() {
         let (name, value) = l.split_once(';').expect("Missing first ';' separator");
-        let (size, alignment) = value.split_once(';').expect("Missing second ';' separator");
+        // Field offsets only have one value
+        let Some((size, alignment)) = value.split_once(';') else {
+            let offset: usize = value.parse().expect("Failed to parse offset");
+            c_offsets.push((name.to_owned(), offset));
+            continue;
+        };
         let size = size.parse().expect("Failed to parse size");
         let alignment = alignment.parse().expect("Failed to parse alignment");
         c_layouts.push((name.to_owned(), Layout { size, alignment }));
@@ -497,15 +816,36 @@ fn cross_validate_layout_with_c() {
         results.record_passed();
     }
 
+    for ((rust_name, rust_offset), (c_name, c_offset)) in
+        rust_offsets().iter().zip(c_offsets.iter())
+    {
+        if rust_name != c_name {
+            results.record_failed();
+            eprintln!("Name mismatch:\nRust: {rust_name:?}\nC:    {c_name:?}");
+            continue;
+        }
+
+        if rust_offset != c_offset {
+            results.record_failed();
+            eprintln!(
+                "Offset mismatch for {rust_name}\nRust: {rust_offset:?}\nC:    {c_offset:?}",
+            );
+            continue;
+        }
+
+        results.record_passed();
+    }
+
     results.expect_total_success();
 }
 
-fn get_c_output(name: &str) -> Result<String, Box<dyn Error>> {
+fn get_c_output(name: &str, args: &[&str]) -> Result<String, Box<dyn Error>> {
     let tmpdir = Builder::new().prefix("abi").tempdir()?;
     let exe = tmpdir.path().join(name);
     let c_file = Path::new("tests").join(name).with_extension("c");
 
-    let cc = Compiler::new().expect("configured compiler");
+    let mut cc = Compiler::new().expect("configured compiler");
+    cc.args.extend(args.iter().map(|&arg| arg.to_owned()));
     cc.compile(&c_file, &exe)?;
 
     let mut cmd = Command::new(exe);
@@ -530,6 +870,47 @@ const RUST_LAYOUTS: &[(&str, Layout)] = &["#
         w,
         "{}",
         r#"];
+"#
+    )?;
+    if !cfields.is_empty() {
+        writeln!(
+            w,
+            "{}",
+            r#"/// Same as `std::mem::offset_of!`, which needs Rust 1.77.
+macro_rules! field_offset {
+    ($type:ty, $field:ident) => {{
+        let value = std::mem::MaybeUninit::<$type>::uninit();
+        let base = value.as_ptr();
+        // Only the address of the field is taken, nothing is read
+        let field = unsafe { std::ptr::addr_of!((*base).$field) };
+        field as usize - base as usize
+    }};
+}
+"#
+        )?;
+    }
+    writeln!(
+        w,
+        "{}",
+        r#"fn rust_offsets() -> Vec<(&'static str, usize)> {
+    let offsets: &[(&str, usize)] = &["#
+    )?;
+    for cfield in cfields {
+        general::cfg_condition(w, cfield.cfg_condition.as_ref(), false, 2)?;
+        writeln!(
+            w,
+            "        (\"{parent}.{c_name}\", field_offset!({parent}, {name})),",
+            parent = cfield.parent,
+            c_name = cfield.c_name,
+            name = cfield.name
+        )?;
+    }
+    writeln!(
+        w,
+        "{}",
+        r#"    ];
+    offsets.to_vec()
+}
 
 const RUST_CONSTANTS: &[(&str, &str)] = &["#
     )?;
@@ -549,6 +930,48 @@ const RUST_CONSTANTS: &[(&str, &str)] = &["#
         "{}",
         r#"];
 
+const RUST_FUNCTIONS: &[(&str, &[usize])] = &["#
+    )?;
+    for cfunction in cfunctions {
+        general::version_condition_no_doc(w, env, None, cfunction.version, false, 1)?;
+        writeln!(
+            w,
+            "    (\"{}\", &[{}]),",
+            cfunction.name,
+            cfunction
+                .rust_types
+                .iter()
+                .map(|rust_type| format!("size_of::<{rust_type}>()"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+    }
+    writeln!(
+        w,
+        "{}",
+        r#"];
+
+const FUNCTION_C_ARGS: &[&str] = &[
+    // Mismatching prototypes are only warned about by default
+    "-Werror=incompatible-pointer-types","#
+    )?;
+    let mut versions = cfunctions
+        .iter()
+        .filter_map(|cfunction| cfunction.version)
+        .collect::<Vec<_>>();
+    versions.sort();
+    versions.dedup();
+    for version in versions {
+        if let Some(define) = version_define(env, Some(version)) {
+            general::version_condition_no_doc(w, env, None, Some(version), false, 1)?;
+            writeln!(w, "    \"-D{define}\",")?;
+        }
+    }
+    writeln!(
+        w,
+        "{}",
+        r#"];
+
 "#
     )
 }
@@ -40,7 +40,7 @@ pub fn generate(
 
     let prepend = tabs(indent);
     let params_str = trampoline_parameters(env, analysis);
-    let func_str = func_string(env, analysis, fn_self_bound, true);
+    let func_str = func_string(env, analysis, fn_self_bound, true, false);
     let ret_str = trampoline_returns(env, analysis);
 
     writeln!(
@@ -54,10 +54,21 @@ pub fn generate(
         use_glib_if_needed(env, "ffi::gpointer"),
         ret_str,
     )?;
-    writeln!(w, "{prepend}\tlet f: &F = &*(f as *const F);")?;
-    transformation_vars(w, env, analysis, &prepend)?;
+    // Edition 2024 doesn't allow unsafe operations directly in `unsafe fn`
+    let unsafe_block = env.config.edition.is_2024();
+    let body_prepend = if unsafe_block {
+        writeln!(w, "{prepend}\tunsafe {{")?;
+        format!("{prepend}\t")
+    } else {
+        prepend.clone()
+    };
+    writeln!(w, "{body_prepend}\tlet f: &F = &*(f as *const F);")?;
+    transformation_vars(w, env, analysis, &body_prepend)?;
     let call = trampoline_call_func(env, analysis, in_trait);
-    writeln!(w, "{prepend}\t{call}")?;
+    writeln!(w, "{body_prepend}\t{call}")?;
+    if unsafe_block {
+        writeln!(w, "{prepend}\t}}")?;
+    }
     writeln!(w, "{prepend}}}")?;
 
     Ok(())
@@ -68,8 +79,9 @@ pub fn func_string(
     analysis: &Trampoline,
     replace_self_bound: Option<impl AsRef<str>>,
     closure: bool,
+    detail: bool,
 ) -> String {
-    let param_str = func_parameters(env, analysis, replace_self_bound, closure);
+    let param_str = func_parameters(env, analysis, replace_self_bound, closure, detail);
     let return_str = func_returns(env, analysis);
 
     if closure {
@@ -98,6 +110,7 @@ fn func_parameters(
     analysis: &Trampoline,
     replace_self_bound: Option<impl AsRef<str>>,
     closure: bool,
+    detail: bool,
 ) -> String {
     let mut param_str = String::with_capacity(100);
 
@@ -106,6 +119,10 @@ fn func_parameters(
             if let Some(replace_self_bound) = &replace_self_bound {
                 param_str.push_str(par.ref_mode.for_rust_type());
                 param_str.push_str(replace_self_bound.as_ref());
+                // The signal detail follows the receiver
+                if detail {
+                    param_str.push_str(", detail: Option<&str>");
+                }
                 continue;
             }
         } else {
@@ -160,7 +177,7 @@ fn func_returns(env: &Env, analysis: &Trampoline) -> String {
     }
 }
 
-fn trampoline_parameters(env: &Env, analysis: &Trampoline) -> String {
+pub fn trampoline_parameters(env: &Env, analysis: &Trampoline) -> String {
     if analysis.is_notify {
         return format!(
             "{}, _param_spec: {}",
@@ -183,7 +200,7 @@ fn trampoline_parameter(env: &Env, par: &CParameter) -> String {
     format!("{}: {}", par.name, ffi_type.into_string())
 }
 
-fn trampoline_returns(env: &Env, analysis: &Trampoline) -> String {
+pub fn trampoline_returns(env: &Env, analysis: &Trampoline) -> String {
     if analysis.ret.typ == Default::default() {
         String::new()
     } else {
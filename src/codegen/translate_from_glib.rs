@@ -1,8 +1,11 @@
 use crate::{
     analysis::{
-        self, conversion_type::ConversionType, rust_type::RustType, try_from_glib::TryFromGlib,
+        self, conversion_type::ConversionType, ffi_type::ffi_type,
+        functions::is_array_with_scalar_elements, return_value::ZeroCopy, rust_type::RustType,
+        try_from_glib::TryFromGlib,
     },
     chunk::conversion_from_glib::Mode,
+    codegen::return_value::borrowed_type,
     env::Env,
     library,
     nameutil::use_glib_type,
@@ -58,6 +61,30 @@ impl TranslateFromGlib for Mode {
             Pointer => {
                 let trans = from_glib_xxx(self.transfer, array_length);
                 match env.type_(self.typ) {
+                    // Filled in place
+                    library::Type::FixedArray(..) => {
+                        if is_array_with_scalar_elements(env, self.typ) {
+                            (String::new(), ".map(|v| from_glib(v))".into())
+                        } else {
+                            (String::new(), String::new())
+                        }
+                    }
+                    library::Type::CArray(inner_tid)
+                        if is_array_with_scalar_elements(env, self.typ) =>
+                    {
+                        // Enumerations and booleans are `c_int`, flags `c_uint`
+                        let c_type = match env.type_(*inner_tid) {
+                            library::Type::Bitfield(_) => "u32",
+                            _ => "i32",
+                        };
+                        (
+                            format!("Vec::<{c_type}>::{}", trans.0),
+                            format!("{}.into_iter().map(|v| from_glib(v)).collect()", trans.1),
+                        )
+                    }
+                    library::Type::HashTable(key_tid, value_tid) => {
+                        hash_table_from_glib(env, *key_tid, *value_tid, self.transfer, false)
+                    }
                     library::Type::List(..)
                     | library::Type::SList(..)
                     | library::Type::PtrArray(..)
@@ -84,6 +111,33 @@ impl TranslateFromGlib for analysis::return_value::Info {
         array_length: Option<&str>,
     ) -> (String, String) {
         match self.parameter {
+            Some(_) if self.zero_copy.is_some() => {
+                bytes_from_glib(env, self.zero_copy.unwrap(), array_length)
+            }
+            Some(ref par) if self.borrow => {
+                if let library::Type::HashTable(key_tid, value_tid) = env.type_(par.lib_par.typ) {
+                    return hash_table_from_glib(
+                        env,
+                        *key_tid,
+                        *value_tid,
+                        par.lib_par.transfer,
+                        true,
+                    );
+                }
+                let from_ptr = match env.type_(par.lib_par.typ) {
+                    library::Type::Basic(library::Basic::Utf8) => "from_ptr",
+                    _ => "from_glib_ptr_borrow",
+                };
+                let from_ptr = format!("{}::{from_ptr}", borrowed_type(env, par.lib_par.typ));
+                if *par.lib_par.nullable {
+                    (
+                        String::new(),
+                        format!(".as_ref().map(|ptr| {from_ptr}(ptr))"),
+                    )
+                } else {
+                    (format!("{from_ptr}("), ")".into())
+                }
+            }
             Some(ref par) => match self.base_tid {
                 Some(tid) => {
                     let rust_type = RustType::builder(env, tid)
@@ -147,9 +201,143 @@ impl TranslateFromGlib for analysis::return_value::Info {
     }
 }
 
+/// Hands the returned buffer over to a `glib::Bytes` or a `glib::Slice`, or
+/// borrows it.
+fn bytes_from_glib(env: &Env, zero_copy: ZeroCopy, array_length: Option<&str>) -> (String, String) {
+    let length = || array_length_value(array_length.expect("zero_copy buffer without length"));
+    match zero_copy {
+        ZeroCopy::Buffer => (
+            format!(
+                "from_glib_full({}(",
+                use_glib_type(env, "ffi::g_bytes_new_take")
+            ),
+            format!(" as *mut _, {}))", length()),
+        ),
+        ZeroCopy::BorrowedBuffer => (
+            format!(
+                "{}::<u8>::from_glib_borrow_num(",
+                use_glib_type(env, "Slice")
+            ),
+            format!(" as *const _, {})", length()),
+        ),
+        ZeroCopy::ByteArray => (
+            format!(
+                "from_glib_full({}(",
+                use_glib_type(env, "ffi::g_byte_array_free_to_bytes")
+            ),
+            " as *mut _))".into(),
+        ),
+        // Only the `GArray` itself is freed, its elements are kept
+        ZeroCopy::Array => (
+            "{ let array = ".into(),
+            format!(
+                "; let len = (*array).len as usize; {}::from_glib_full_num({}(array, {}) as *mut _, len) }}",
+                use_glib_type(env, "Slice"),
+                use_glib_type(env, "ffi::g_array_free"),
+                use_glib_type(env, "ffi::GFALSE")
+            ),
+        ),
+    }
+}
+
+/// Copies the entries of the returned `GHashTable` into a `HashMap`, the
+/// strings are borrowed from the table when `borrow` is set.
+fn hash_table_from_glib(
+    env: &Env,
+    key_tid: library::TypeId,
+    value_tid: library::TypeId,
+    transfer: library::Transfer,
+    borrow: bool,
+) -> (String, String) {
+    let map_type = format!(
+        "std::collections::HashMap::<{}, {}>",
+        hash_table_element_type(env, key_tid, borrow),
+        hash_table_element_type(env, value_tid, borrow)
+    );
+    // The copied elements are released along with the table
+    let unref = if transfer == library::Transfer::None {
+        String::new()
+    } else {
+        format!(" {}(table);", use_glib_type(env, "ffi::g_hash_table_unref"))
+    };
+    (
+        "{ let table = ".into(),
+        format!(
+            "; let mut map = {map_type}::new(); \
+             if !table.is_null() {{ \
+             let mut iter = std::mem::MaybeUninit::uninit(); \
+             {iter_init}(iter.as_mut_ptr(), table); \
+             let mut key = std::ptr::null_mut(); \
+             let mut value = std::ptr::null_mut(); \
+             while from_glib({iter_next}(iter.as_mut_ptr(), &mut key, &mut value)) {{ \
+             map.insert({key}, {value}); \
+             }}{unref} \
+             }} \
+             map }}",
+            iter_init = use_glib_type(env, "ffi::g_hash_table_iter_init"),
+            iter_next = use_glib_type(env, "ffi::g_hash_table_iter_next"),
+            key = hash_table_element_from_glib(env, key_tid, "key", borrow),
+            value = hash_table_element_from_glib(env, value_tid, "value", borrow),
+        ),
+    )
+}
+
+/// Type of the elements of a map converted from a `GHashTable`.
+pub fn hash_table_element_type(env: &Env, tid: library::TypeId, borrow: bool) -> String {
+    match env.type_(tid) {
+        library::Type::Basic(library::Basic::Utf8) if borrow => {
+            format!("&{}", use_glib_type(env, "GStr"))
+        }
+        library::Type::Basic(library::Basic::Utf8) => "String".into(),
+        _ => RustType::try_new(env, tid).into_string(),
+    }
+}
+
+/// Converts the pointer `ptr` stored in a `GHashTable`, the integers are
+/// stored in the pointers themselves.
+fn hash_table_element_from_glib(
+    env: &Env,
+    tid: library::TypeId,
+    ptr: &str,
+    borrow: bool,
+) -> String {
+    use crate::library::{Basic::*, Type::*};
+    match env.type_(tid) {
+        Basic(Utf8) if borrow => format!(
+            "{}::from_ptr({ptr} as *const _)",
+            use_glib_type(env, "GStr")
+        ),
+        Basic(Utf8) => format!("from_glib_none({ptr} as *const std::ffi::c_char)"),
+        Basic(Int | Int32) => format!("{ptr} as isize as i32"),
+        Basic(UInt | UInt32) => format!("{ptr} as usize as u32"),
+        Basic(Boolean) => format!(
+            "from_glib({ptr} as isize as {})",
+            use_glib_type(env, "ffi::gboolean")
+        ),
+        Enumeration(library::Enumeration { c_type, .. }) => format!(
+            "from_glib({ptr} as isize as {})",
+            ffi_type(env, tid, c_type).into_string()
+        ),
+        Bitfield(library::Bitfield { c_type, .. }) => format!(
+            "from_glib({ptr} as usize as {})",
+            ffi_type(env, tid, c_type).into_string()
+        ),
+        _ => format!(
+            "from_glib_none({ptr} as {})",
+            ffi_type(env, tid, "gpointer").into_string()
+        ),
+    }
+}
+
+/// The length of an array, returned through an out parameter initialized by
+/// the call.
+fn array_length_value(name: &str) -> String {
+    format!("{name}.assume_init() as _")
+}
+
 fn from_glib_xxx(transfer: library::Transfer, array_length: Option<&str>) -> (String, String) {
     use crate::library::Transfer;
-    let good_print = |name: &str| format!(", {name}.assume_init() as _)");
+    let good_print = |name: &str| format!(", {})", array_length_value(name));
     match (transfer, array_length) {
         (Transfer::None, None) => ("from_glib_none(".into(), ")".into()),
         (Transfer::Full, None) => ("from_glib_full(".into(), ")".into()),
@@ -1,6 +1,9 @@
 use crate::{
-    analysis::{function_parameters::TransformationType, ref_mode::RefMode},
-    library::Transfer,
+    analysis::{ffi_type::ffi_type, function_parameters::TransformationType, ref_mode::RefMode},
+    env::Env,
+    library::{self, Transfer},
+    nameutil::use_glib_type,
+    traits::*,
 };
 
 pub trait TranslateToGlib {
@@ -46,6 +49,8 @@ impl TranslateToGlib for TransformationType {
                     format!("{left}{name}{to_glib_extra}{right}{pointer_cast}")
                 }
             }
+            // Shadowed by the table filled before the call
+            ToGlibHashTable { ref name, .. } => name.clone(),
             ToGlibBorrow => "/*Not applicable conversion Borrow*/".to_owned(),
             ToGlibUnknown { ref name } => format!("/*Unknown conversion*/{name}"),
             ToSome(ref name) => format!("Some({name})"),
@@ -94,3 +99,69 @@ fn to_glib_xxx(
         Container => ("".into(), ".to_glib_container().0"),
     }
 }
+
+/// Fills a new `GHashTable` with copies of the entries of the map `name`, the
+/// table owns them and releases them when it is destroyed.
+pub fn hash_table_to_glib(env: &Env, name: &str, typ: library::TypeId) -> String {
+    let library::Type::HashTable(key_tid, value_tid) = *env.type_(typ) else {
+        panic!("{name} isn't a hash table");
+    };
+    let (hash, equal) = match env.type_(key_tid) {
+        library::Type::Basic(library::Basic::Utf8) => ("g_str_hash", "g_str_equal"),
+        _ => ("g_direct_hash", "g_direct_equal"),
+    };
+    format!(
+        "{{ let map = {name}; \
+         let table = {new_full}(Some({hash}), Some({equal}), {key_destroy}, {value_destroy}); \
+         for (key, value) in map {{ \
+         {insert}(table, {key}, {value}); \
+         }} \
+         table }}",
+        new_full = use_glib_type(env, "ffi::g_hash_table_new_full"),
+        hash = use_glib_type(env, &format!("ffi::{hash}")),
+        equal = use_glib_type(env, &format!("ffi::{equal}")),
+        key_destroy = hash_table_element_destroy(env, key_tid),
+        value_destroy = hash_table_element_destroy(env, value_tid),
+        insert = use_glib_type(env, "ffi::g_hash_table_insert"),
+        key = hash_table_element_to_glib(env, key_tid, "key"),
+        value = hash_table_element_to_glib(env, value_tid, "value"),
+    )
+}
+
+/// Destroy notify of the elements owned by a `GHashTable`, the integers
+/// aren't allocated.
+fn hash_table_element_destroy(env: &Env, tid: library::TypeId) -> String {
+    match env.type_(tid) {
+        library::Type::Basic(library::Basic::Utf8) => {
+            format!("Some({})", use_glib_type(env, "ffi::g_free"))
+        }
+        library::Type::Class(..) | library::Type::Interface(..) => format!(
+            "Some(std::mem::transmute::<unsafe extern \"C\" fn(*mut {}), unsafe extern \"C\" fn({})>({}))",
+            use_glib_type(env, "gobject_ffi::GObject"),
+            use_glib_type(env, "ffi::gpointer"),
+            use_glib_type(env, "gobject_ffi::g_object_unref"),
+        ),
+        _ => "None".into(),
+    }
+}
+
+/// Converts the borrowed element `var` of a map into a pointer owned by a
+/// `GHashTable`, the integers are stored in the pointers themselves.
+fn hash_table_element_to_glib(env: &Env, tid: library::TypeId, var: &str) -> String {
+    use crate::library::{Basic::*, Type::*};
+    let gpointer = use_glib_type(env, "ffi::gpointer");
+    match env.type_(tid) {
+        Basic(Utf8) => format!(
+            "{}({var}.as_ptr() as *const _, {var}.len()) as {gpointer}",
+            use_glib_type(env, "ffi::g_strndup")
+        ),
+        Basic(Int | Int32) => format!("*{var} as isize as {gpointer}"),
+        Basic(UInt | UInt32) => format!("*{var} as usize as {gpointer}"),
+        Bitfield(..) => format!("{var}.into_glib() as usize as {gpointer}"),
+        Basic(Boolean) | Enumeration(..) => format!("{var}.into_glib() as isize as {gpointer}"),
+        _ => format!(
+            "ToGlibPtr::<{}>::to_glib_full({var}) as {gpointer}",
+            ffi_type(env, tid, "gpointer").into_string()
+        ),
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::{Result, Write};
+
+use super::{function, general, trait_impls, translate_from_glib::TranslateFromGlib};
+use crate::{analysis, chunk::conversion_from_glib::Mode, env::Env, library, version::Version};
+
+pub fn generate(w: &mut dyn Write, env: &Env, analysis: &analysis::union::Info) -> Result<()> {
+    let type_ = analysis.type_(&env.library);
+
+    general::start_comments(w, &env.config)?;
+    general::uses(w, env, &analysis.imports, analysis.version)?;
+
+    general::define_auto_boxed_type(
+        w,
+        env,
+        &analysis.name,
+        type_.c_type.as_ref().unwrap(),
+        analysis.boxed_inline,
+        &analysis.init_function_expression,
+        &analysis.copy_into_function_expression,
+        &analysis.clear_function_expression,
+        &analysis.glib_get_type,
+        &analysis.derives,
+        analysis.visibility,
+        analysis.type_id,
+    )?;
+
+    if !analysis.fields.is_empty()
+        || analysis
+            .functions
+            .iter()
+            .any(|f| f.status.need_generate() && !f.hidden)
+    {
+        writeln!(w)?;
+        write!(w, "impl {} {{", analysis.name)?;
+
+        for func_analysis in &analysis.functions {
+            function::generate(
+                w,
+                env,
+                Some(analysis.type_id),
+                func_analysis,
+                Some(&analysis.specials),
+                analysis.version,
+                false,
+                false,
+                1,
+            )?;
+        }
+
+        for field in &analysis.fields {
+            generate_field_getter(w, env, analysis, field)?;
+        }
+
+        writeln!(w, "}}")?;
+    }
+
+    general::declare_default_from_new(w, env, &analysis.name, &analysis.functions, false)?;
+
+    trait_impls::generate(
+        w,
+        env,
+        &analysis.name,
+        &analysis.functions,
+        &analysis.specials,
+        None,
+        analysis.version,
+        None, // There is no need for #[cfg()] since it's applied on the whole file.
+    )?;
+
+    if analysis.concurrency != library::Concurrency::None {
+        writeln!(w)?;
+    }
+
+    match analysis.concurrency {
+        library::Concurrency::Send | library::Concurrency::SendSync => {
+            writeln!(w, "unsafe impl Send for {} {{}}", analysis.name)?;
+        }
+        _ => (),
+    }
+
+    if analysis.concurrency == library::Concurrency::SendSync {
+        writeln!(w, "unsafe impl Sync for {} {{}}", analysis.name)?;
+    }
+
+    Ok(())
+}
+
+fn generate_field_getter(
+    w: &mut dyn Write,
+    env: &Env,
+    analysis: &analysis::union::Info,
+    field: &analysis::fields::Info,
+) -> Result<()> {
+    let (prefix, suffix) = Mode {
+        typ: field.typ,
+        transfer: library::Transfer::None,
+        try_from_glib: Default::default(),
+    }
+    .translate_from_glib_as_function(env, None);
+    let value = format!("{prefix}(*self.as_ptr()).{}{suffix}", field.name);
+    let version = Version::if_stricter_than(field.version, analysis.version);
+
+    writeln!(w)?;
+    general::cfg_condition(w, field.cfg_condition.as_ref(), false, 1)?;
+    general::version_condition(w, env, None, version, false, 1)?;
+    // Only the tag tells which of the other fields holds a valid value
+    if analysis.tag_field.as_ref() == Some(&field.name) {
+        writeln!(
+            w,
+            "\tpub fn {}(&self) -> {} {{",
+            field.name, field.rust_type
+        )?;
+        writeln!(w, "\t\tunsafe {{ {value} }}")?;
+    } else {
+        writeln!(
+            w,
+            "\tpub unsafe fn {}(&self) -> {} {{",
+            field.name, field.rust_type
+        )?;
+        writeln!(w, "\t\t{}", general::unsafe_in_unsafe_fn(env, &value))?;
+    }
+    writeln!(w, "\t}}")
+}
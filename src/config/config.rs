@@ -10,12 +10,13 @@ use log::warn;
 
 use super::{
     external_libraries::{read_external_libraries, ExternalLibrary},
-    gobjects, WorkMode,
+    gobjects, Edition, WorkMode,
 };
 use crate::{
     analysis::namespaces::{self, Namespace, NsId},
     config::error::TomlHelper,
     env::Env,
+    fmt::Formatter,
     git::{repo_hash, repo_remote_url, toplevel},
     library::{self, Library},
     nameutil::set_crate_name_overrides,
@@ -107,6 +108,9 @@ pub struct Config {
     pub min_cfg_version: Version,
     pub use_gi_docgen: bool,
     pub make_backup: bool,
+    /// Only compare the generated files with the ones on disk instead of
+    /// writing them
+    pub check: bool,
     pub generate_safety_asserts: bool,
     pub deprecate_by_min_version: bool,
     pub show_statistics: bool,
@@ -114,7 +118,15 @@ pub struct Config {
     pub single_version_file: Option<PathBuf>,
     pub trust_return_value_nullability: bool,
     pub disable_format: bool,
+    /// Fail instead of writing the unformatted file when `rustfmt` fails
+    pub fail_on_format_error: bool,
+    pub formatter: Formatter,
     pub split_build_rs: bool,
+    /// Load the shared libraries at runtime in the `-sys` crate instead of
+    /// linking to them
+    pub dynamic_loading: bool,
+    /// Edition of the generated code, also written in new `-sys` manifests
+    pub edition: Edition,
     pub extra_versions: Vec<Version>,
     pub lib_version_overrides: HashMap<Version, Version>,
     pub feature_dependencies: HashMap<Version, Vec<String>>,
@@ -136,6 +148,7 @@ impl Config {
         make_backup: bool,
         show_statistics: bool,
         disable_format: bool,
+        check: bool,
     ) -> Result<Self, String>
     where
         S: Into<Option<&'a str>>,
@@ -234,11 +247,6 @@ impl Config {
             None if work_mode == WorkMode::Normal => target_path.join("src").join("auto"),
             None => target_path.join("src"),
         };
-        if work_mode == WorkMode::Normal && auto_path.exists() {
-            std::fs::remove_dir_all(&auto_path)
-                .map_err(|e| format!("remove_dir_all failed: {e:?}"))?;
-        }
-
         let doc_target_path: PathBuf = match doc_target_path.into() {
             Some("") | None => match toml.lookup("options.doc_target_path") {
                 Some(p) => config_dir.join(p.as_result_str("options.doc_target_path")?),
@@ -321,12 +329,26 @@ impl Config {
                 None => true,
             }
         };
+        let fail_on_format_error = match toml.lookup("options.fail_on_format_error") {
+            Some(v) => v.as_result_bool("options.fail_on_format_error")?,
+            None => false,
+        };
+        let edition = match toml.lookup("options.edition") {
+            Some(v) => v.as_result_str("options.edition")?.parse()?,
+            None => Edition::default(),
+        };
+        let formatter = Formatter::discover(&target_path, edition);
 
         let split_build_rs = match toml.lookup("options.split_build_rs") {
             Some(v) => v.as_result_bool("options.split_build_rs")?,
             None => false,
         };
 
+        let dynamic_loading = match toml.lookup("options.dynamic_loading") {
+            Some(v) => v.as_result_bool("options.dynamic_loading")?,
+            None => false,
+        };
+
         let extra_versions = read_extra_versions(&toml)?;
         let lib_version_overrides = read_lib_version_overrides(&toml)?;
         let feature_dependencies = read_feature_dependencies(&toml)?;
@@ -346,6 +368,7 @@ impl Config {
             min_cfg_version,
             use_gi_docgen,
             make_backup,
+            check,
             generate_safety_asserts,
             deprecate_by_min_version,
             show_statistics,
@@ -353,7 +376,11 @@ impl Config {
             single_version_file,
             trust_return_value_nullability,
             disable_format,
+            fail_on_format_error,
+            formatter,
             split_build_rs,
+            dynamic_loading,
+            edition,
             extra_versions,
             lib_version_overrides,
             feature_dependencies,
@@ -396,11 +423,15 @@ impl Config {
         gobjects::resolve_type_ids(&mut self.objects, library);
     }
 
-    pub fn check_disable_format(&mut self) {
+    pub fn check_disable_format(&mut self) -> Result<(), String> {
         if !self.disable_format && !crate::fmt::check_fmt() {
+            if self.fail_on_format_error {
+                return Err("Formatter not found".to_owned());
+            }
             warn!("Formatter not found, options.disable_format set to true");
             self.disable_format = true;
         }
+        Ok(())
     }
 }
 
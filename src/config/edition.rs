@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+/// Rust edition the generated code targets.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Edition {
+    #[default]
+    E2021,
+    /// `extern` blocks must be `unsafe` and unsafe operations in an
+    /// `unsafe fn` need their own `unsafe` block.
+    E2024,
+}
+
+impl Edition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::E2021 => "2021",
+            Self::E2024 => "2024",
+        }
+    }
+
+    pub fn is_2024(self) -> bool {
+        self >= Self::E2024
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "2021" => Ok(Self::E2021),
+            "2024" => Ok(Self::E2024),
+            _ => Err(format!("Unsupported edition '{s}'")),
+        }
+    }
+}
@@ -9,6 +9,55 @@ pub struct ExternalLibrary {
     pub crate_name: String,
     pub lib_name: String,
     pub min_version: Option<Version>,
+    /// Dependency on the `-sys` crate, written as is in its `Cargo.toml`
+    pub dependency: Option<Dependency>,
+}
+
+/// Cargo dependency specification.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Dependency {
+    /// Name of the package, if it's not the one of the dependency
+    pub package: Option<String>,
+    pub version: Option<String>,
+    pub path: Option<String>,
+    pub registry: Option<String>,
+    pub git: Option<String>,
+    pub features: Vec<String>,
+    pub optional: bool,
+}
+
+impl Dependency {
+    pub fn to_toml(&self) -> toml::Value {
+        let mut table = toml::value::Table::new();
+        let strings = [
+            ("package", &self.package),
+            ("version", &self.version),
+            ("path", &self.path),
+            ("registry", &self.registry),
+            ("git", &self.git),
+        ];
+        for (key, value) in strings {
+            if let Some(value) = value {
+                table.insert(key.to_owned(), toml::Value::String(value.clone()));
+            }
+        }
+        if !self.features.is_empty() {
+            table.insert(
+                "features".to_owned(),
+                toml::Value::Array(
+                    self.features
+                        .iter()
+                        .cloned()
+                        .map(toml::Value::String)
+                        .collect(),
+                ),
+            );
+        }
+        if self.optional {
+            table.insert("optional".to_owned(), toml::Value::Boolean(true));
+        }
+        toml::Value::Table(table)
+    }
 }
 
 pub fn read_external_libraries(toml: &toml::Value) -> Result<Vec<ExternalLibrary>, String> {
@@ -16,17 +65,31 @@ pub fn read_external_libraries(toml: &toml::Value) -> Result<Vec<ExternalLibrary
         Some(a) => a
             .as_result_vec("options.external_libraries")?
             .iter()
-            .filter_map(|v| v.as_str().map(String::from))
-            .map(|namespace| {
-                let crate_name_ = crate_name(&namespace);
-                ExternalLibrary {
-                    crate_name: crate_name_.clone(),
-                    lib_name: crate_name_,
-                    min_version: None,
-                    namespace,
+            .filter_map(|v| match v {
+                toml::Value::String(namespace) => {
+                    let crate_name_ = crate_name(namespace);
+                    Some(Ok(ExternalLibrary {
+                        crate_name: crate_name_.clone(),
+                        lib_name: crate_name_,
+                        min_version: None,
+                        namespace: namespace.clone(),
+                        dependency: None,
+                    }))
                 }
+                toml::Value::Table(info) => Some(
+                    info.get("namespace")
+                        .ok_or_else(|| {
+                            "No `namespace` given for an entry of `options.external_libraries`"
+                                .to_owned()
+                        })
+                        .and_then(|namespace| {
+                            namespace.as_result_str("options.external_libraries.namespace")
+                        })
+                        .and_then(|namespace| read_external_library(namespace, info)),
+                ),
+                _ => None,
             })
-            .collect(),
+            .collect::<Result<_, _>>()?,
         None => Vec::new(),
     };
     let custom_libs = toml
@@ -35,29 +98,16 @@ pub fn read_external_libraries(toml: &toml::Value) -> Result<Vec<ExternalLibrary
     if let Some(custom_libs) = custom_libs {
         for custom_lib in custom_libs {
             if let Some(info) = custom_lib.1.as_table() {
-                let namespace = custom_lib.0.as_str();
-                let crate_name_ = info.get("crate").map_or_else(
-                    || crate_name(namespace),
-                    |c| c.as_str().expect("crate name must be a string").to_string(),
-                );
-                let min_version = info
-                    .get("min_version")
-                    .map(|v| v.as_str().expect("min required version must be a string"))
-                    .map(|v| Version::from_str(v).expect("Invalid version number"));
-                let lib = ExternalLibrary {
-                    namespace: namespace.to_owned(),
-                    crate_name: crate_name_,
-                    lib_name: crate_name(namespace),
-                    min_version,
-                };
+                let lib = read_external_library(custom_lib.0, info)?;
                 external_libraries.push(lib);
             } else if let Some(namespace) = custom_lib.1.as_str() {
                 let crate_name_ = custom_lib.0;
                 let lib = ExternalLibrary {
                     namespace: namespace.to_owned(),
                     crate_name: crate_name_.clone(),
-                    lib_name: crate_name(custom_lib.1.as_str().expect("No custom lib name set")),
+                    lib_name: crate_name(namespace),
                     min_version: None,
+                    dependency: None,
                 };
                 external_libraries.push(lib);
             } else {
@@ -72,6 +122,76 @@ pub fn read_external_libraries(toml: &toml::Value) -> Result<Vec<ExternalLibrary
     Ok(external_libraries)
 }
 
+fn read_external_library(
+    namespace: &str,
+    info: &toml::value::Table,
+) -> Result<ExternalLibrary, String> {
+    let option = |key: &str| format!("external library {namespace} `{key}`");
+    let crate_name_ = match info.get("crate") {
+        Some(c) => c.as_result_str(&option("crate"))?.to_owned(),
+        None => crate_name(namespace),
+    };
+    let min_version = match info.get("min_version") {
+        Some(v) => Some(
+            Version::from_str(v.as_result_str(&option("min_version"))?)
+                .map_err(|e| format!("Invalid {}: {e}", option("min_version")))?,
+        ),
+        None => None,
+    };
+    Ok(ExternalLibrary {
+        namespace: namespace.to_owned(),
+        crate_name: crate_name_,
+        lib_name: crate_name(namespace),
+        min_version,
+        dependency: read_dependency(namespace, info)?,
+    })
+}
+
+fn read_dependency(
+    namespace: &str,
+    info: &toml::value::Table,
+) -> Result<Option<Dependency>, String> {
+    const KEYS: [&str; 7] = [
+        "package", "version", "path", "registry", "git", "features", "optional",
+    ];
+    if !KEYS.iter().any(|key| info.contains_key(*key)) {
+        return Ok(None);
+    }
+
+    let option = |key: &str| format!("external library {namespace} `{key}`");
+    let string = |key: &str| {
+        info.get(key)
+            .map(|v| v.as_result_str(&option(key)).map(ToOwned::to_owned))
+            .transpose()
+    };
+    let features = match info.get("features") {
+        Some(features) => features
+            .as_result_vec(&option("features"))?
+            .iter()
+            .map(|feature| {
+                feature
+                    .as_result_str(&option("features"))
+                    .map(ToOwned::to_owned)
+            })
+            .collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+    let optional = match info.get("optional") {
+        Some(optional) => optional.as_result_bool(&option("optional"))?,
+        None => false,
+    };
+
+    Ok(Some(Dependency {
+        package: string("package")?,
+        version: string("version")?,
+        path: string("path")?,
+        registry: string("registry")?,
+        git: string("git")?,
+        features,
+        optional,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +227,7 @@ other-lib="OtherLib"
                 crate_name: "glib".to_owned(),
                 lib_name: "glib".to_owned(),
                 min_version: None,
+                dependency: None,
             }
         );
         assert_eq!(
@@ -116,6 +237,7 @@ other-lib="OtherLib"
                 crate_name: "gdk".to_owned(),
                 lib_name: "gdk".to_owned(),
                 min_version: None,
+                dependency: None,
             }
         );
         assert_eq!(
@@ -125,6 +247,7 @@ other-lib="OtherLib"
                 crate_name: "gdk_pixbuf".to_owned(),
                 lib_name: "gdk_pixbuf".to_owned(),
                 min_version: None,
+                dependency: None,
             }
         );
         // Sorted alphabetically
@@ -135,6 +258,7 @@ other-lib="OtherLib"
                 crate_name: "coollib".to_owned(),
                 lib_name: "cool_lib".to_owned(),
                 min_version: None,
+                dependency: None,
             }
         );
         assert_eq!(
@@ -144,6 +268,7 @@ other-lib="OtherLib"
                 crate_name: "other-lib".to_owned(),
                 lib_name: "other_lib".to_owned(),
                 min_version: None,
+                dependency: None,
             }
         );
     }
@@ -167,6 +292,7 @@ OtherLib={min_version = "0.4.0"}
                 crate_name: "coollib".to_owned(),
                 lib_name: "cool_lib".to_owned(),
                 min_version: Some(Version::from_str("0.3.0").unwrap()),
+                dependency: None,
             }
         );
         assert_eq!(
@@ -176,7 +302,84 @@ OtherLib={min_version = "0.4.0"}
                 crate_name: "other_lib".to_owned(),
                 lib_name: "other_lib".to_owned(),
                 min_version: Some(Version::from_str("0.4.0").unwrap()),
+                dependency: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_external_libraries_with_dependency() {
+        let toml = toml(
+            r#"
+[options]
+external_libraries = [
+   "GLib",
+   { namespace = "CoolLib", crate = "coollib", version = "0.3", registry = "internal" },
+]
+
+[external_libraries]
+OtherLib = { min_version = "0.4.0", path = "../other-lib/sys", features = ["v0_4"], optional = true }
+"#,
+        );
+        let libs = read_external_libraries(&toml).unwrap();
+
+        assert_eq!(libs[0].dependency, None);
+        assert_eq!(
+            libs[1],
+            ExternalLibrary {
+                namespace: "CoolLib".to_owned(),
+                crate_name: "coollib".to_owned(),
+                lib_name: "cool_lib".to_owned(),
+                min_version: None,
+                dependency: Some(Dependency {
+                    version: Some("0.3".to_owned()),
+                    registry: Some("internal".to_owned()),
+                    ..Default::default()
+                }),
             }
         );
+        assert_eq!(
+            libs[2].dependency,
+            Some(Dependency {
+                path: Some("../other-lib/sys".to_owned()),
+                features: vec!["v0_4".to_owned()],
+                optional: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            libs[2].dependency.as_ref().unwrap().to_toml().to_string(),
+            r#"{ path = "../other-lib/sys", features = ["v0_4"], optional = true }"#
+        );
+    }
+
+    #[test]
+    fn test_read_external_libraries_without_namespace() {
+        let toml = toml(
+            r#"
+[options]
+external_libraries = [{ crate = "coollib" }]
+"#,
+        );
+        assert!(read_external_libraries(&toml).is_err());
+    }
+
+    #[test]
+    fn test_read_external_libraries_with_invalid_values() {
+        let config = toml(
+            r#"
+[external_libraries]
+CoolLib = { crate = 3 }
+"#,
+        );
+        assert!(read_external_libraries(&config).is_err());
+
+        let config = toml(
+            r#"
+[external_libraries]
+CoolLib = { min_version = 0.2 }
+"#,
+        );
+        assert!(read_external_libraries(&config).is_err());
     }
 }
@@ -0,0 +1,136 @@
+use log::error;
+use toml::Value;
+
+use super::{error::TomlHelper, gobjects::GStatus, ident::Ident, parsable::Parse};
+use crate::{library::Nullable, version::Version};
+
+/// Public field of a record to generate accessors for.
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub ident: Ident,
+    pub status: GStatus,
+    pub version: Option<Version>,
+    pub cfg_condition: Option<String>,
+    /// Only used for pointer fields, which are nullable by default.
+    pub nullable: Option<Nullable>,
+    pub setter: bool,
+}
+
+impl Parse for Field {
+    fn parse(toml: &Value, object_name: &str) -> Option<Self> {
+        let Some(ident) = Ident::parse(toml, object_name, "field") else {
+            error!(
+                "No 'name' or 'pattern' given for field for object {}",
+                object_name
+            );
+            return None;
+        };
+
+        toml.check_unwanted(
+            &[
+                "name",
+                "pattern",
+                "ignore",
+                "manual",
+                "version",
+                "cfg_condition",
+                "nullable",
+                "setter",
+            ],
+            &format!("field {object_name}"),
+        );
+
+        let status = {
+            if toml
+                .lookup("ignore")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+            {
+                GStatus::Ignore
+            } else if toml
+                .lookup("manual")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+            {
+                GStatus::Manual
+            } else {
+                GStatus::Generate
+            }
+        };
+        let version = toml
+            .lookup("version")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse().ok());
+        let cfg_condition = toml
+            .lookup("cfg_condition")
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned);
+        let nullable = toml
+            .lookup("nullable")
+            .and_then(Value::as_bool)
+            .map(Nullable);
+        let setter = toml
+            .lookup("setter")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+
+        Some(Self {
+            ident,
+            status,
+            version,
+            cfg_condition,
+            nullable,
+            setter,
+        })
+    }
+}
+
+impl AsRef<Ident> for Field {
+    fn as_ref(&self) -> &Ident {
+        &self.ident
+    }
+}
+
+pub type Fields = Vec<Field>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{ident::Ident, parsable::Parse},
+        *,
+    };
+
+    fn toml(input: &str) -> ::toml::Value {
+        let value = ::toml::from_str(input);
+        assert!(value.is_ok());
+        value.unwrap()
+    }
+
+    #[test]
+    fn field_parse_default() {
+        let toml = toml(
+            r#"
+name = "x"
+"#,
+        );
+        let f = Field::parse(&toml, "a").unwrap();
+        assert_eq!(f.ident, Ident::Name("x".into()));
+        assert!(f.status.need_generate());
+        assert_eq!(f.nullable, None);
+        assert!(f.setter);
+    }
+
+    #[test]
+    fn field_parse_options() {
+        let toml = toml(
+            r#"
+pattern = "v_.*"
+nullable = false
+setter = false
+"#,
+        );
+        let f = Field::parse(&toml, "a").unwrap();
+        assert_eq!(f.nullable, Some(Nullable(false)));
+        assert!(!f.setter);
+    }
+}
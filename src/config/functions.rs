@@ -163,6 +163,10 @@ pub struct Return {
     pub use_return_for_result: Option<bool>,
     pub string_type: Option<StringType>,
     pub type_name: Option<String>,
+    /// Hand the returned buffer over to a `glib::Bytes` instead of copying it.
+    pub zero_copy: bool,
+    /// Return a reference living as long as the instance instead of a copy.
+    pub borrow: bool,
 }
 
 impl Return {
@@ -177,6 +181,8 @@ impl Return {
                 use_return_for_result: None,
                 string_type: None,
                 type_name: None,
+                zero_copy: false,
+                borrow: false,
             };
         }
 
@@ -191,6 +197,8 @@ impl Return {
                 "use_return_for_result",
                 "string_type",
                 "type",
+                "zero_copy",
+                "borrow",
             ],
             "return",
         );
@@ -235,6 +243,11 @@ impl Return {
                 object_name
             );
         }
+        let zero_copy = v
+            .lookup("zero_copy")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let borrow = v.lookup("borrow").and_then(Value::as_bool).unwrap_or(false);
 
         Self {
             nullable,
@@ -245,6 +258,8 @@ impl Return {
             use_return_for_result,
             string_type,
             type_name,
+            zero_copy,
+            borrow,
         }
     }
 }
@@ -278,6 +293,9 @@ pub struct Function {
     pub doc_trait_name: Option<String>,
     pub doc_struct_name: Option<String>,
     pub no_future: bool,
+    /// Generate a `*_stream()` variant sending the arguments of the callback
+    /// to a `Stream`
+    pub stream: bool,
     pub unsafe_: bool,
     pub rename: Option<String>,
     pub visibility: Option<Visibility>,
@@ -285,6 +303,8 @@ pub struct Function {
     pub is_constructor: Option<bool>,
     pub assertion: Option<SafetyAssertionMode>,
     pub generate_doc: bool,
+    /// Overrides the `introspectable` attribute of the GIR function
+    pub introspectable: Option<bool>,
 }
 
 impl Parse for Function {
@@ -312,6 +332,7 @@ impl Parse for Function {
                 "doc_trait_name",
                 "doc_struct_name",
                 "no_future",
+                "stream",
                 "unsafe",
                 "rename",
                 "bypass_auto_rename",
@@ -319,6 +340,7 @@ impl Parse for Function {
                 "assertion",
                 "visibility",
                 "generate_doc",
+                "introspectable",
             ],
             &format!("function {object_name}"),
         );
@@ -378,6 +400,10 @@ impl Parse for Function {
             .lookup("no_future")
             .and_then(Value::as_bool)
             .unwrap_or(false);
+        let stream = toml
+            .lookup("stream")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
         let unsafe_ = toml
             .lookup("unsafe")
             .and_then(Value::as_bool)
@@ -416,6 +442,7 @@ impl Parse for Function {
             .lookup("generate_doc")
             .and_then(Value::as_bool)
             .unwrap_or(true);
+        let introspectable = toml.lookup("introspectable").and_then(Value::as_bool);
         Some(Self {
             ident,
             status,
@@ -429,6 +456,7 @@ impl Parse for Function {
             doc_trait_name,
             doc_struct_name,
             no_future,
+            stream,
             unsafe_,
             rename,
             visibility,
@@ -436,6 +464,7 @@ impl Parse for Function {
             is_constructor,
             assertion,
             generate_doc,
+            introspectable,
         })
     }
 }
@@ -660,6 +689,26 @@ name = "prop"
         assert!(f.generate_doc);
     }
 
+    #[test]
+    fn function_parse_introspectable() {
+        let r = toml(
+            r#"
+name = "func"
+introspectable = true
+"#,
+        );
+        let f = Function::parse(&r, "a").unwrap();
+        assert_eq!(f.introspectable, Some(true));
+
+        let r = toml(
+            r#"
+name = "func"
+"#,
+        );
+        let f = Function::parse(&r, "a").unwrap();
+        assert_eq!(f.introspectable, None);
+    }
+
     #[test]
     fn functions_parse_empty_for_none() {
         let fns = Functions::parse(None, "a");
@@ -958,4 +1007,55 @@ name = "func1"
         let param1 = &f.parameters[0];
         assert_eq!(param1.infallible, Some(Infallible(false)));
     }
+
+    #[test]
+    fn parse_stream() {
+        let toml = toml(
+            r#"
+name = "func1"
+stream = true
+"#,
+        );
+        let f = Function::parse(&toml, "a").unwrap();
+        assert!(f.stream);
+        assert!(!f.no_future);
+    }
+
+    #[test]
+    fn parse_return_zero_copy() {
+        let toml = toml(
+            r#"
+name = "func1"
+    [return]
+    zero_copy = true
+"#,
+        );
+        let f = Function::parse(&toml, "a").unwrap();
+        assert!(f.ret.zero_copy);
+    }
+
+    #[test]
+    fn parse_return_zero_copy_default() {
+        let toml = toml(
+            r#"
+name = "func1"
+"#,
+        );
+        let f = Function::parse(&toml, "a").unwrap();
+        assert!(!f.ret.zero_copy);
+    }
+
+    #[test]
+    fn parse_return_borrow() {
+        let toml = toml(
+            r#"
+name = "func1"
+    [return]
+    borrow = true
+"#,
+        );
+        let f = Function::parse(&toml, "a").unwrap();
+        assert!(f.ret.borrow);
+        assert!(!f.ret.zero_copy);
+    }
 }
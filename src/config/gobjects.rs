@@ -11,6 +11,7 @@ use super::{
     child_properties::ChildProperties,
     constants::Constants,
     derives::Derives,
+    fields::Fields,
     functions::Functions,
     ident::Ident,
     members::Members,
@@ -70,6 +71,7 @@ pub struct GObject {
     pub constants: Constants,
     pub signals: Signals,
     pub members: Members,
+    pub fields: Fields,
     pub properties: Properties,
     pub derives: Option<Derives>,
     pub status: GStatus,
@@ -90,6 +92,9 @@ pub struct GObject {
     pub manual_traits: Vec<String>,
     pub align: Option<u32>,
     pub generate_builder: bool,
+    pub generate_subclass: bool,
+    pub generate_property_default_tests: bool,
+    pub generate_emit_signals: bool,
     pub builder_postprocess: Option<String>,
     pub boxed_inline: bool,
     pub init_function_expression: Option<String>,
@@ -98,6 +103,7 @@ pub struct GObject {
     pub visibility: Visibility,
     pub default_value: Option<String>,
     pub generate_doc: bool,
+    pub tag_field: Option<String>,
 }
 
 impl Default for GObject {
@@ -109,6 +115,7 @@ impl Default for GObject {
             constants: Constants::new(),
             signals: Signals::new(),
             members: Members::new(),
+            fields: Fields::new(),
             properties: Properties::new(),
             derives: None,
             status: Default::default(),
@@ -129,6 +136,9 @@ impl Default for GObject {
             manual_traits: Vec::default(),
             align: None,
             generate_builder: false,
+            generate_subclass: false,
+            generate_property_default_tests: false,
+            generate_emit_signals: false,
             builder_postprocess: None,
             boxed_inline: false,
             init_function_expression: None,
@@ -137,6 +147,7 @@ impl Default for GObject {
             visibility: Default::default(),
             default_value: None,
             generate_doc: true,
+            tag_field: None,
         }
     }
 }
@@ -243,9 +254,11 @@ fn parse_object(
             "name",
             "status",
             "function",
+            "virtual_method",
             "constant",
             "signal",
             "member",
+            "field",
             "property",
             "derive",
             "module_name",
@@ -267,6 +280,9 @@ fn parse_object(
             "manual_traits",
             "align",
             "generate_builder",
+            "generate_subclass",
+            "generate_property_default_tests",
+            "generate_emit_signals",
             "builder_postprocess",
             "boxed_inline",
             "init_function_expression",
@@ -275,6 +291,7 @@ fn parse_object(
             "visibility",
             "default_value",
             "generate_doc",
+            "tag_field",
         ],
         &format!("object {name}"),
     );
@@ -318,6 +335,7 @@ fn parse_object(
         v
     };
     let members = Members::parse(toml_object.lookup("member"), &name);
+    let fields = Fields::parse(toml_object.lookup("field"), &name);
     let properties = Properties::parse(toml_object.lookup("property"), &name);
     let derives = toml_object
         .lookup("derive")
@@ -393,6 +411,18 @@ fn parse_object(
         .lookup("generate_builder")
         .and_then(Value::as_bool)
         .unwrap_or(generate_builder);
+    let generate_subclass = toml_object
+        .lookup("generate_subclass")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let generate_property_default_tests = toml_object
+        .lookup("generate_property_default_tests")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let generate_emit_signals = toml_object
+        .lookup("generate_emit_signals")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
 
     let boxed_inline = toml_object
         .lookup("boxed_inline")
@@ -471,6 +501,10 @@ fn parse_object(
         .lookup("generate_doc")
         .and_then(Value::as_bool)
         .unwrap_or(true);
+    let tag_field = toml_object
+        .lookup("tag_field")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
 
     if generate_trait.is_some() {
         warn!(
@@ -486,6 +520,7 @@ fn parse_object(
         constants,
         signals,
         members,
+        fields,
         properties,
         derives,
         status,
@@ -506,6 +541,9 @@ fn parse_object(
         manual_traits,
         align,
         generate_builder,
+        generate_subclass,
+        generate_property_default_tests,
+        generate_emit_signals,
         builder_postprocess,
         boxed_inline,
         init_function_expression,
@@ -514,6 +552,7 @@ fn parse_object(
         visibility,
         default_value,
         generate_doc,
+        tag_field,
     }
 }
 
@@ -772,4 +811,91 @@ status = "generate"
         let object = parse_object(r, Concurrency::default(), false, false);
         assert!(object.generate_doc);
     }
+
+    #[test]
+    fn generate_subclass() {
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+generate_subclass = true
+    [[virtual_method]]
+    name = "activate"
+    manual = true
+"#,
+        );
+
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert!(object.generate_subclass);
+        assert!(object.virtual_methods[0].status.manual());
+
+        // Ensure that the default value is "false".
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+"#,
+        );
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert!(!object.generate_subclass);
+    }
+
+    #[test]
+    fn generate_property_default_tests() {
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+generate_property_default_tests = true
+"#,
+        );
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert!(object.generate_property_default_tests);
+
+        // Ensure that the default value is "false".
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+"#,
+        );
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert!(!object.generate_property_default_tests);
+    }
+
+    #[test]
+    fn generate_emit_signals() {
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+generate_emit_signals = true
+"#,
+        );
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert!(object.generate_emit_signals);
+
+        // Ensure that the default value is "false".
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+"#,
+        );
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert!(!object.generate_emit_signals);
+    }
+
+    #[test]
+    fn tag_field() {
+        let r = &toml(
+            r#"
+name = "Test"
+status = "generate"
+tag_field = "type"
+"#,
+        );
+        let object = parse_object(r, Concurrency::default(), false, false);
+        assert_eq!(object.tag_field.as_deref(), Some("type"));
+    }
 }
@@ -3,8 +3,10 @@ mod child_properties;
 pub mod config;
 pub mod constants;
 pub mod derives;
+pub mod edition;
 pub mod error;
-mod external_libraries;
+pub mod external_libraries;
+pub mod fields;
 pub mod functions;
 pub mod gobjects;
 pub mod ident;
@@ -22,6 +24,7 @@ pub mod work_mode;
 pub use self::{
     child_properties::{ChildProperties, ChildProperty},
     config::Config,
+    edition::Edition,
     gobjects::GObject,
     property_generate_flags::PropertyGenerateFlags,
     string_type::StringType,
@@ -107,6 +107,7 @@ pub struct Signal {
     pub doc_hidden: bool,
     pub doc_trait_name: Option<String>,
     pub generate_doc: bool,
+    pub generate_emit: Option<bool>,
 }
 
 impl Signal {
@@ -136,6 +137,7 @@ impl Signal {
                 "concurrency",
                 "doc_trait_name",
                 "generate_doc",
+                "generate_emit",
             ],
             &format!("signal {object_name}"),
         );
@@ -187,6 +189,7 @@ impl Signal {
             .lookup("generate_doc")
             .and_then(Value::as_bool)
             .unwrap_or(true);
+        let generate_emit = toml.lookup("generate_emit").and_then(Value::as_bool);
 
         Some(Self {
             ident,
@@ -199,6 +202,7 @@ impl Signal {
             doc_hidden,
             doc_trait_name,
             generate_doc,
+            generate_emit,
         })
     }
 }
@@ -285,4 +289,24 @@ name = "prop"
         let f = Signal::parse(&r, "a", Default::default()).unwrap();
         assert!(f.generate_doc);
     }
+
+    #[test]
+    fn signal_parse_generate_emit() {
+        let r = toml(
+            r#"
+name = "signal1"
+generate_emit = true
+"#,
+        );
+        let f = Signal::parse(&r, "a", Default::default()).unwrap();
+        assert_eq!(f.generate_emit, Some(true));
+
+        let r = toml(
+            r#"
+name = "signal1"
+"#,
+        );
+        let f = Signal::parse(&r, "a", Default::default()).unwrap();
+        assert_eq!(f.generate_emit, None);
+    }
 }
@@ -3,6 +3,7 @@ use std::cell::RefCell;
 use crate::{
     analysis::{self, namespaces::NsId},
     config::{gobjects::GStatus, Config},
+    file_saver,
     library::*,
     nameutil::use_glib_type,
     version::Version,
@@ -16,6 +17,7 @@ pub struct Env {
     pub symbols: RefCell<analysis::symbols::Info>,
     pub class_hierarchy: analysis::class_hierarchy::Info,
     pub analysis: analysis::Analysis,
+    pub saved_files: RefCell<file_saver::Report>,
 }
 
 impl Env {
@@ -1,29 +1,194 @@
 use std::{
-    fs::{self, File},
-    io::{BufWriter, Result, Write},
-    path::Path,
+    collections::HashMap,
+    fs,
+    io::{Result, Write},
+    path::{Path, PathBuf},
 };
 
-use crate::writer::untabber::Untabber;
+use log::{info, warn};
+use similar::TextDiff;
 
-pub fn save_to_file<P, F>(path: P, make_backup: bool, mut closure: F)
+use crate::{env::Env, writer::untabber::Untabber};
+
+/// What happened to the files touched during a run.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub created: Vec<PathBuf>,
+    pub changed: Vec<PathBuf>,
+    pub unchanged: Vec<PathBuf>,
+    /// Files left over from a previous run that were not produced anymore.
+    pub removed: Vec<PathBuf>,
+    /// New content of the created and changed files when running with
+    /// `--check`, as nothing gets written then.
+    pub pending: HashMap<PathBuf, Vec<u8>>,
+}
+
+impl Report {
+    fn is_saved(&self, path: &Path) -> bool {
+        self.created
+            .iter()
+            .chain(&self.changed)
+            .chain(&self.unchanged)
+            .any(|p| p == path)
+    }
+
+    pub fn print(&self) {
+        println!(
+            "Files: {} created, {} changed, {} unchanged, {} removed",
+            self.created.len(),
+            self.changed.len(),
+            self.unchanged.len(),
+            self.removed.len()
+        );
+        for (action, paths) in [
+            ("created", &self.created),
+            ("changed", &self.changed),
+            ("removed", &self.removed),
+        ] {
+            for path in paths {
+                println!("  {action}: {}", path.display());
+            }
+        }
+    }
+
+    /// Prints an unified diff for every file that would be created, changed
+    /// or removed and returns how many there are.
+    pub fn print_diff(&self) -> usize {
+        for path in &self.created {
+            print_diff(None, Some(path), &[], &self.pending[path]);
+        }
+        for path in &self.changed {
+            let old = fs::read(path).unwrap_or_default();
+            print_diff(Some(path), Some(path), &old, &self.pending[path]);
+        }
+        for path in &self.removed {
+            let old = fs::read(path).unwrap_or_default();
+            print_diff(Some(path), None, &old, &[]);
+        }
+        self.created.len() + self.changed.len() + self.removed.len()
+    }
+}
+
+fn print_diff(old_path: Option<&Path>, new_path: Option<&Path>, old: &[u8], new: &[u8]) {
+    let header = |path: Option<&Path>| path.map_or("/dev/null".into(), |p| p.display().to_string());
+    let (old_header, new_header) = (header(old_path), header(new_path));
+    let old = String::from_utf8_lossy(old);
+    let new = String::from_utf8_lossy(new);
+    let diff = TextDiff::from_lines(&old, &new)
+        .unified_diff()
+        .header(&old_header, &new_header)
+        .to_string();
+    if diff.is_empty() {
+        // Added or removed empty file, there are no hunks to show
+        println!("--- {old_header}\n+++ {new_header}");
+    } else {
+        print!("{diff}");
+    }
+}
+
+/// Renders the file in memory and only writes it when its content differs
+/// from what is already on disk, so unchanged files keep their timestamps.
+pub fn save_to_file<P, F>(env: &Env, path: P, mut closure: F)
 where
     P: AsRef<Path>,
     F: FnMut(&mut dyn Write) -> Result<()>,
 {
     let path = path.as_ref();
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
+    if !env.config.check {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+
+    let mut content = Vec::new();
+    closure(&mut Untabber::new(&mut content))
+        .unwrap_or_else(|why| panic!("couldn't write to {path:?}: {why:?}"));
+
+    if !env.config.disable_format && path.extension().is_some_and(|ext| ext == "rs") {
+        match env.config.formatter.format(&content) {
+            Ok(formatted) => content = formatted,
+            Err(why) if env.config.fail_on_format_error => {
+                panic!("couldn't format {path:?}:\n{why}")
+            }
+            Err(why) => warn!("Failed to format {}:\n{}", path.display(), why),
+        }
     }
 
-    if make_backup {
+    let mut report = env.saved_files.borrow_mut();
+    match fs::read(path) {
+        Ok(old) if old == content => {
+            report.unchanged.push(path.to_owned());
+            return;
+        }
+        Ok(_) => report.changed.push(path.to_owned()),
+        Err(_) => report.created.push(path.to_owned()),
+    }
+    if env.config.check {
+        report.pending.insert(path.to_owned(), content);
+        return;
+    }
+
+    if env.config.make_backup {
         let _backuped = create_backup(path)
             .unwrap_or_else(|why| panic!("couldn't create backup for {path:?}: {why:?}"));
     }
-    let file = File::create(path).unwrap_or_else(|why| panic!("couldn't create {path:?}: {why}"));
-    let writer = BufWriter::new(file);
-    let mut untabber = Untabber::new(Box::new(writer));
-    closure(&mut untabber).unwrap_or_else(|why| panic!("couldn't write to {path:?}: {why:?}"));
+    fs::write(path, content).unwrap_or_else(|why| panic!("couldn't create {path:?}: {why}"));
+}
+
+/// Removes the files in `dir` that weren't saved during this run, together
+/// with the directories that end up empty.
+pub fn remove_stale_files(env: &Env, dir: &Path) {
+    let mut report = env.saved_files.borrow_mut();
+    let mut stale = Vec::new();
+    collect_stale_files(dir, &report, &mut stale);
+
+    if env.config.check {
+        report.removed = stale;
+        return;
+    }
+
+    for path in stale {
+        info!("Removing stale file {path:?}");
+        let removed = if env.config.make_backup {
+            create_backup(&path).map(|_| ())
+        } else {
+            fs::remove_file(&path)
+        };
+        removed.unwrap_or_else(|why| panic!("couldn't remove {path:?}: {why}"));
+        report.removed.push(path);
+    }
+    remove_empty_dirs(dir);
+}
+
+fn collect_stale_files(dir: &Path, report: &Report, stale: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<_>>();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_stale_files(&path, report, stale);
+        } else if path.extension() != Some("bak".as_ref()) && !report.is_saved(&path) {
+            stale.push(path);
+        }
+    }
+}
+
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            // Fails for directories that still have content, which is fine
+            let _ = fs::remove_dir(&path);
+        }
+    }
 }
 
 /// Create .bak file
@@ -1,10 +1,17 @@
-use std::{path::Path, process::Command};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
 
 use log::warn;
 
-/// Check if `cargo fmt` available
+use crate::config::{error::TomlHelper, Edition};
+
+/// Check if `rustfmt` available
 pub fn check_fmt() -> bool {
-    let output = Command::new("cargo").arg("fmt").arg("--version").output();
+    let output = Command::new("rustfmt").arg("--version").output();
     if let Ok(output) = output {
         output.status.success()
     } else {
@@ -12,19 +19,159 @@ pub fn check_fmt() -> bool {
     }
 }
 
-/// Run `cargo fmt` on path
-pub fn format(path: &Path) {
-    let output = Command::new("cargo").arg("fmt").current_dir(path).output();
-    match output {
-        Ok(output) if output.status.success() => {}
-        Ok(output) => {
-            warn!(
-                "Failed to format {}:\n{}\n{}",
-                path.display(),
-                String::from_utf8_lossy(&output.stdout),
-                String::from_utf8_lossy(&output.stderr)
-            );
+/// Formats generated files with `rustfmt` the same way `cargo fmt` would
+/// in the target crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Formatter {
+    pub edition: String,
+    /// The `rustfmt.toml` (or `.rustfmt.toml`) applying to the target crate.
+    pub config_path: Option<PathBuf>,
+}
+
+impl Formatter {
+    /// The configured `edition` is used when the target crate doesn't have a
+    /// `Cargo.toml` yet, matching the one written in generated `-sys`
+    /// manifests.
+    pub fn discover(target_path: &Path, edition: Edition) -> Self {
+        // Relative paths wouldn't let us look at the parent directories
+        let target_path = target_path
+            .canonicalize()
+            .unwrap_or_else(|_| target_path.to_owned());
+        Self {
+            edition: discover_edition(&target_path, edition),
+            config_path: discover_config(&target_path),
+        }
+    }
+
+    pub fn format(&self, source: &[u8]) -> Result<Vec<u8>, String> {
+        let mut command = Command::new("rustfmt");
+        command.args(["--edition", &self.edition]);
+        if let Some(config_path) = &self.config_path {
+            command.arg("--config-path").arg(config_path);
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run rustfmt: {e}"))?;
+
+        // rustfmt only starts writing once stdin is closed, so the pipes can't
+        // fill up while we are still writing
+        child
+            .stdin
+            .take()
+            .expect("piped stdin")
+            .write_all(source)
+            .map_err(|e| format!("failed to write to rustfmt: {e}"))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("failed to run rustfmt: {e}"))?;
+
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).into_owned())
+        }
+    }
+}
+
+/// Edition set by a `Cargo.toml`.
+#[derive(Debug, PartialEq, Eq)]
+enum ManifestEdition {
+    Edition(String),
+    /// `edition.workspace = true`, the edition comes from the workspace
+    /// manifest.
+    Workspace,
+}
+
+fn manifest_edition(manifest: &toml::Value, in_workspace: bool) -> Option<ManifestEdition> {
+    if in_workspace {
+        return manifest
+            .lookup("workspace.package.edition")
+            .and_then(toml::Value::as_str)
+            .map(|edition| ManifestEdition::Edition(edition.to_owned()));
+    }
+    manifest.lookup("package")?;
+    match manifest.lookup("package.edition") {
+        Some(toml::Value::String(edition)) => Some(ManifestEdition::Edition(edition.clone())),
+        Some(edition)
+            if edition
+                .lookup("workspace")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false) =>
+        {
+            Some(ManifestEdition::Workspace)
+        }
+        Some(_) => None,
+        // Cargo's default when no edition is given
+        None => Some(ManifestEdition::Edition("2015".to_owned())),
+    }
+}
+
+fn discover_edition(target_path: &Path, default: Edition) -> String {
+    let mut in_workspace = false;
+    for dir in target_path.ancestors() {
+        let path = dir.join("Cargo.toml");
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let manifest = match toml::from_str(&content) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!("Failed to parse {}: {e}", path.display());
+                continue;
+            }
+        };
+        match manifest_edition(&manifest, in_workspace) {
+            Some(ManifestEdition::Edition(edition)) => return edition,
+            Some(ManifestEdition::Workspace) => in_workspace = true,
+            None => {}
         }
-        Err(_) => { /*We checked `cargo` fmt presence in check_fmt, so can ignore errors*/ }
+    }
+    default.as_str().to_owned()
+}
+
+fn discover_config(target_path: &Path) -> Option<PathBuf> {
+    target_path.ancestors().find_map(|dir| {
+        ["rustfmt.toml", ".rustfmt.toml"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edition(toml: &str, in_workspace: bool) -> Option<ManifestEdition> {
+        manifest_edition(&toml::from_str(toml).unwrap(), in_workspace)
+    }
+
+    #[test]
+    fn manifest_edition_package() {
+        assert_eq!(
+            edition("[package]\nname = \"a\"\nedition = \"2018\"", false),
+            Some(ManifestEdition::Edition("2018".to_owned()))
+        );
+        assert_eq!(
+            edition("[package]\nname = \"a\"", false),
+            Some(ManifestEdition::Edition("2015".to_owned()))
+        );
+        assert_eq!(edition("[workspace]\nmembers = []", false), None);
+    }
+
+    #[test]
+    fn manifest_edition_workspace() {
+        assert_eq!(
+            edition("[package]\nname = \"a\"\nedition.workspace = true", false),
+            Some(ManifestEdition::Workspace)
+        );
+        assert_eq!(
+            edition("[workspace.package]\nedition = \"2021\"", true),
+            Some(ManifestEdition::Edition("2021".to_owned()))
+        );
+        assert_eq!(edition("[package]\nname = \"a\"", true), None);
     }
 }
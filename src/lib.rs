@@ -42,7 +42,7 @@ pub use crate::{
         run as analysis_run, symbols::run as symbols_run,
     },
     codegen::generate as codegen_generate,
-    config::{Config, WorkMode},
+    config::{Config, Edition, WorkMode},
     env::Env,
     library::Library,
 };
@@ -118,6 +118,31 @@ impl FromStr for ParameterScope {
     }
 }
 
+/// Stage of the signal emission during which the class handler runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignalEmissionStage {
+    /// Before the handlers connected with `g_signal_connect()`.
+    First,
+    /// After the handlers connected with `g_signal_connect()` and before the
+    /// ones connected with `g_signal_connect_after()`.
+    Last,
+    /// After all the handlers.
+    Cleanup,
+}
+
+impl FromStr for SignalEmissionStage {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match name {
+            "first" => Ok(Self::First),
+            "last" => Ok(Self::Last),
+            "cleanup" => Ok(Self::Cleanup),
+            _ => Err(format!("Unknown signal emission stage '{name}'")),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Nullable(pub bool);
 
@@ -518,6 +543,9 @@ pub struct Property {
     pub doc_deprecated: Option<String>,
     pub getter: Option<String>,
     pub setter: Option<String>,
+    /// Default value as written in the `default-value` attribute, e.g.
+    /// `FALSE`, `0` or `GTK_ALIGN_FILL`.
+    pub default_value: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -558,6 +586,11 @@ pub struct Function {
     pub finish_func: Option<String>,
     pub async_func: Option<String>,
     pub sync_func: Option<String>,
+    pub introspectable: bool,
+    /// Name of the function that should be bound in place of this one.
+    pub shadowed_by: Option<String>,
+    /// Name of the function this one is bound in place of.
+    pub shadows: Option<String>,
 }
 
 #[derive(Debug)]
@@ -567,6 +600,10 @@ pub struct Signal {
     pub ret: Parameter,
     pub is_action: bool,
     pub is_detailed: bool,
+    pub when: Option<SignalEmissionStage>,
+    pub no_recurse: bool,
+    pub no_hooks: bool,
+    pub no_detail: bool,
     pub version: Option<Version>,
     pub deprecated_version: Option<Version>,
     pub doc: Option<String>,
@@ -1407,4 +1444,12 @@ mod tests {
         assert_eq!(TypeId::tid_filename().full_name(&lib), "*.Filename");
         assert_eq!(TypeId::tid_os_string().full_name(&lib), "*.OsString");
     }
+
+    #[test]
+    fn signal_emission_stage() {
+        assert_eq!("first".parse(), Ok(SignalEmissionStage::First));
+        assert_eq!("last".parse(), Ok(SignalEmissionStage::Last));
+        assert_eq!("cleanup".parse(), Ok(SignalEmissionStage::Cleanup));
+        assert!("never".parse::<SignalEmissionStage>().is_err());
+    }
 }
@@ -7,7 +7,7 @@ use crate::{
     config::{
         gobjects::{GObject, GStatus},
         matchable::Matchable,
-        Config, WorkMode,
+        Config, Edition, WorkMode,
     },
     library::*,
     nameutil,
@@ -39,7 +39,7 @@ impl Library {
         self.fill_empty_signals_c_types();
         self.resolve_class_structs();
         self.correlate_class_structs();
-        self.fix_fields();
+        self.fix_fields(config.edition);
         self.make_unrepresentable_types_opaque();
         self.mark_final_types(config);
         self.update_error_domain_functions(config);
@@ -288,7 +288,7 @@ impl Library {
         }
     }
 
-    fn fix_fields(&mut self) {
+    fn fix_fields(&mut self, edition: Edition) {
         enum Action {
             SetCType(String),
             SetName(String),
@@ -306,8 +306,9 @@ impl Library {
                     | Type::Record(Record { name, fields, .. })
                     | Type::Union(Union { name, fields, .. }) => {
                         for (fid, field) in fields.iter().enumerate() {
-                            if nameutil::needs_mangling(&field.name) {
-                                let new_name = nameutil::mangle_keywords(&*field.name).into_owned();
+                            if nameutil::needs_mangling(&field.name, edition) {
+                                let new_name =
+                                    nameutil::mangle_keywords(&*field.name, edition).into_owned();
                                 actions.push((tid, fid, Action::SetName(new_name)));
                             }
                             if field.c_type.is_some() {
@@ -22,8 +22,16 @@ impl<S: AsRef<str>> OptionStr for Option<S> {
     }
 }
 
+/// Output of the `not_bound` work mode.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum NotBoundFormat {
+    Text,
+    Json,
+}
+
 enum RunKind {
-    Config(Config),
+    /// The C identifier of the function to explain is given with the config.
+    Config(Config, NotBoundFormat, Option<String>),
     CheckGirFile(String),
 }
 
@@ -56,6 +64,24 @@ fn build_config() -> Result<RunKind, String> {
     options.optflag("b", "make-backup", "Make backup before generating");
     options.optflag("s", "stats", "Show statistics");
     options.optflag("", "disable-format", "Disable formatting generated code");
+    options.optflag(
+        "",
+        "check",
+        "Fail if the generated files differ from the ones on disk, without writing them",
+    );
+    options.optopt(
+        "",
+        "not-bound-format",
+        "Output of the not_bound mode: text (default) or json",
+        "FORMAT",
+    );
+    options.optopt(
+        "",
+        "explain",
+        "Show the config rules and the reasons deciding how a function is generated, \
+         without generating anything",
+        "C_IDENTIFIER",
+    );
     options.optopt(
         "",
         "check-gir-file",
@@ -85,6 +111,12 @@ fn build_config() -> Result<RunKind, String> {
         },
     };
 
+    let not_bound_format = match matches.opt_str("not-bound-format").as_deref() {
+        None | Some("text") => NotBoundFormat::Text,
+        Some("json") => NotBoundFormat::Json,
+        Some(s) => return Err(format!("Wrong not_bound format '{s}'")),
+    };
+
     Config::new(
         matches.opt_str("c").as_str_ref(),
         work_mode,
@@ -96,8 +128,9 @@ fn build_config() -> Result<RunKind, String> {
         matches.opt_present("b"),
         matches.opt_present("s"),
         matches.opt_present("disable-format"),
+        matches.opt_present("check"),
     )
-    .map(RunKind::Config)
+    .map(|config| RunKind::Config(config, not_bound_format, matches.opt_str("explain")))
 }
 
 fn run_check(check_gir_file: &str) -> Result<(), String> {
@@ -125,12 +158,15 @@ fn main() -> Result<(), String> {
     }
     env_logger::init();
 
-    let mut cfg = match build_config() {
+    let (mut cfg, not_bound_format, explain) = match build_config() {
         Ok(RunKind::CheckGirFile(check_gir_file)) => return run_check(&check_gir_file),
-        Ok(RunKind::Config(cfg)) => cfg,
+        Ok(RunKind::Config(cfg, not_bound_format, explain)) => (cfg, not_bound_format, explain),
         Err(err) => return Err(err),
     };
-    cfg.check_disable_format();
+    cfg.check_disable_format()?;
+    if explain.is_some() && cfg.work_mode == WorkMode::Sys {
+        return Err("--explain isn't supported in sys mode".to_owned());
+    }
 
     let statistics = Profiler::new("Gir");
     statistics.start_frame();
@@ -184,6 +220,7 @@ fn main() -> Result<(), String> {
             symbols: RefCell::new(symbols),
             class_hierarchy,
             analysis: Default::default(),
+            saved_files: Default::default(),
         }
     };
 
@@ -192,24 +229,40 @@ fn main() -> Result<(), String> {
         gir::analysis_run(&mut env);
     }
 
+    if let Some(c_identifier) = explain {
+        print!("{}", gir::analysis::explain::explain(&env, &c_identifier)?);
+        return Ok(());
+    }
+
     if env.config.work_mode != WorkMode::DisplayNotBound {
         let _watcher = statistics.enter("Generating");
         gir::codegen_generate(&env);
     }
 
-    if !env.config.disable_format && env.config.work_mode.is_generate_rust_files() {
-        let _watcher = statistics.enter("Formatting");
-        gir::fmt::format(&env.config.target_path);
-    }
-
     drop(watcher_total);
     statistics.end_frame();
 
     if env.config.show_statistics {
         statistics.print_timing();
+        env.saved_files.borrow().print();
     }
     if env.config.work_mode == WorkMode::DisplayNotBound {
-        env.library.show_non_bound_types(&env);
+        match not_bound_format {
+            NotBoundFormat::Text => env.library.show_non_bound_types(&env),
+            NotBoundFormat::Json => {
+                let report = gir::analysis::not_bound::report(&env);
+                let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+                println!("{json}");
+            }
+        }
+    }
+    if env.config.check {
+        let differences = env.saved_files.borrow().print_diff();
+        if differences > 0 {
+            return Err(format!(
+                "{differences} generated file(s) are not up to date"
+            ));
+        }
     }
 
     Ok(())
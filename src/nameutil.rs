@@ -1,6 +1,6 @@
 use std::{borrow::Cow, collections::HashMap, path::*, sync::OnceLock};
 
-use crate::case::*;
+use crate::{case::*, config::Edition};
 
 static mut CRATE_NAME_OVERRIDES: Option<HashMap<String, String>> = None;
 
@@ -70,8 +70,8 @@ pub fn exported_crate_name(crate_name: &str) -> String {
     crate_name.replace('_', "-")
 }
 
-pub fn module_name(name: &str) -> String {
-    mangle_keywords(name.to_snake()).into_owned()
+pub fn module_name(name: &str, edition: Edition) -> String {
+    mangle_keywords(name.to_snake(), edition).into_owned()
 }
 
 pub fn mangle_crate(name: &str) -> String {
@@ -97,7 +97,8 @@ pub fn escape_digit(name: &str) -> String {
     if name.starts_with(char::is_numeric) {
         format!("_{name}")
     } else {
-        mangle_keywords(name).into()
+        // Type names are in camel case unlike the keywords reserved by editions
+        mangle_keywords(name, Edition::default()).into()
     }
 }
 
@@ -129,15 +130,17 @@ pub fn bitfield_member_name(name: &str) -> String {
     }
 }
 
-pub fn needs_mangling(name: &str) -> bool {
-    keywords().contains_key(name)
+pub fn needs_mangling(name: &str, edition: Edition) -> bool {
+    keywords().contains_key(name) || (edition.is_2024() && keywords_2024().contains_key(name))
 }
 
 // If the mangling happened, guaranteed to return Owned.
-pub fn mangle_keywords<'a, S: Into<Cow<'a, str>>>(name: S) -> Cow<'a, str> {
+pub fn mangle_keywords<'a, S: Into<Cow<'a, str>>>(name: S, edition: Edition) -> Cow<'a, str> {
     let name = name.into();
     if let Some(s) = keywords().get(&*name) {
         s.clone().into()
+    } else if let Some(s) = keywords_2024().get(&*name).filter(|_| edition.is_2024()) {
+        s.clone().into()
     } else {
         name
     }
@@ -160,6 +163,12 @@ fn keywords() -> &'static HashMap<&'static str, String> {
     })
 }
 
+/// Keywords reserved since the 2024 edition, valid names before it.
+fn keywords_2024() -> &'static HashMap<&'static str, String> {
+    static KEYWORDS: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+    KEYWORDS.get_or_init(|| ["gen"].iter().map(|k| (*k, format!("{k}_"))).collect())
+}
+
 pub fn signal_to_snake(signal: &str) -> String {
     signal.replace("::", "_").replace('-', "_")
 }
@@ -269,6 +278,17 @@ mod tests {
         assert_eq!(signal_to_snake("move-active"), "move_active");
     }
 
+    #[test]
+    fn mangle_keywords_works() {
+        assert_eq!(mangle_keywords("type", Edition::E2021), "type_");
+        assert_eq!(mangle_keywords("name", Edition::E2024), "name");
+        // Only reserved since the 2024 edition
+        assert_eq!(mangle_keywords("gen", Edition::E2021), "gen");
+        assert_eq!(mangle_keywords("gen", Edition::E2024), "gen_");
+        assert!(!needs_mangling("gen", Edition::E2021));
+        assert!(needs_mangling("gen", Edition::E2024));
+    }
+
     #[test]
     fn lib_name_to_toml_works() {
         assert_eq!(lib_name_to_toml("gstreamer-1.0"), "gstreamer_1_0");
@@ -542,6 +542,7 @@ impl Library {
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
         let c_identifier = elem.attr("identifier").or_else(|| elem.attr("name"));
+        let introspectable = elem.attr_bool("introspectable", true);
         let mut params = Vec::new();
         let mut ret = None;
         let mut doc = None;
@@ -602,6 +603,9 @@ impl Library {
                 finish_func: None,
                 async_func: None,
                 sync_func: None,
+                introspectable,
+                shadowed_by: None,
+                shadows: None,
             })
         } else {
             Err(parser.fail("Missing <return-value> element"))
@@ -1039,6 +1043,9 @@ impl Library {
         });
         let async_func = elem.attr("async-func").map(ToString::to_string);
         let sync_func = elem.attr("sync-func").map(ToString::to_string);
+        let introspectable = elem.attr_bool("introspectable", true);
+        let shadowed_by = elem.attr("shadowed-by").map(ToString::to_string);
+        let shadows = elem.attr("shadows").map(ToString::to_string);
 
         let mut params = Vec::new();
         let mut ret = None;
@@ -1127,6 +1134,9 @@ impl Library {
                 finish_func,
                 async_func,
                 sync_func,
+                introspectable,
+                shadowed_by,
+                shadows,
             })
         } else {
             Err(parser.fail_with_position(
@@ -1184,6 +1194,10 @@ impl Library {
         let signal_name = elem.attr_required("name")?;
         let is_action = elem.attr_bool("action", false);
         let is_detailed = elem.attr_bool("detailed", false);
+        let when = elem.attr_from_str("when")?;
+        let no_recurse = elem.attr_bool("no-recurse", false);
+        let no_hooks = elem.attr_bool("no-hooks", false);
+        let no_detail = elem.attr_bool("no-detail", false);
         let version = self.read_version(parser, ns_id, elem)?;
         let deprecated_version = self.read_deprecated_version(parser, ns_id, elem)?;
 
@@ -1218,6 +1232,10 @@ impl Library {
                 ret,
                 is_action,
                 is_detailed,
+                when,
+                no_recurse,
+                no_hooks,
+                no_detail,
                 version,
                 deprecated_version,
                 doc,
@@ -1362,6 +1380,7 @@ impl Library {
         let writable = elem.attr_bool("writable", false);
         let construct = elem.attr_bool("construct", false);
         let construct_only = elem.attr_bool("construct-only", false);
+        let default_value = elem.attr("default-value").map(ToString::to_string);
         let transfer = Transfer::from_str(elem.attr("transfer-ownership").unwrap_or("none"))
             .map_err(|why| parser.fail_with_position(&why, elem.position()))?;
 
@@ -1445,6 +1464,7 @@ impl Library {
                 doc_deprecated,
                 getter,
                 setter,
+                default_value,
             }))
         } else {
             Err(parser.fail_with_position(
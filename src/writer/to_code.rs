@@ -5,6 +5,7 @@ use crate::{
     chunk::{Chunk, Param, TupleMode},
     codegen::{translate_from_glib::TranslateFromGlib, translate_to_glib::TranslateToGlib},
     env::Env,
+    library,
     nameutil::use_glib_type,
 };
 
@@ -40,7 +41,10 @@ impl ToCode for Chunk {
                 vec![s]
             }
             FfiCallOutParameter { ref par } => {
-                let s = if par.caller_allocates {
+                let s = if matches!(env.type_(par.typ), library::Type::FixedArray(..)) {
+                    // Points to the whole array instead of its first element
+                    format!("{}.as_mut_ptr() as *mut _", par.name)
+                } else if par.caller_allocates {
                     format!("{}.to_glib_none_mut().0", par.name)
                 } else if par.is_uninitialized && !par.is_error {
                     format!("{}.as_mut_ptr()", par.name)
@@ -186,7 +190,12 @@ impl ToCode for Chunk {
                     write!(s, " -> {return_value}").unwrap();
                 }
                 s.push_str(" {");
-                let mut code = format_block("", "}", &body.to_code(env));
+                let mut body = body.to_code(env);
+                // Callbacks are `unsafe fn` too
+                if env.config.edition.is_2024() {
+                    body = format_block("unsafe {", "}", &body);
+                }
+                let mut code = format_block("", "}", &body);
                 code.insert(0, s);
                 code
             }
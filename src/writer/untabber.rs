@@ -2,17 +2,17 @@ use std::io::{Result, Write};
 
 use super::TAB;
 
-pub struct Untabber {
-    orig: Box<dyn Write>,
+pub struct Untabber<W: Write> {
+    orig: W,
 }
 
-impl Untabber {
-    pub fn new(orig: Box<dyn Write>) -> Self {
+impl<W: Write> Untabber<W> {
+    pub fn new(orig: W) -> Self {
         Self { orig }
     }
 }
 
-impl Write for Untabber {
+impl<W: Write> Write for Untabber<W> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         let mut chunks = buf.split(|b| b == &b'\t').peekable();
         while let Some(chunk) = chunks.next() {
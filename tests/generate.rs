@@ -0,0 +1,530 @@
+//! Generates the bindings of the GIR files in `tests/fixtures` and checks the
+//! conversions generated for the container types.
+
+use std::{cell::RefCell, fs, path::PathBuf};
+
+use libgir::{self as gir, Config, Edition, Env, Library, WorkMode};
+
+/// Runs gir in normal mode in a directory named after the test, and returns
+/// the generated `widget.rs`.
+fn generate_widget(test_name: &str) -> String {
+    generate(test_name, "widget.rs")
+}
+
+/// Runs gir in normal mode in a directory named after the test, and returns
+/// the generated `file` of `src/auto`.
+fn generate(test_name: &str, file: &str) -> String {
+    read_generated(&generate_env(test_name), file)
+}
+
+fn read_generated(env: &Env, file: &str) -> String {
+    fs::read_to_string(env.config.target_path.join("src/auto").join(file)).unwrap()
+}
+
+/// Runs gir in normal mode in a directory named after the test, and returns
+/// its environment.
+fn generate_env(test_name: &str) -> Env {
+    generate_env_with(test_name, |_| ())
+}
+
+/// Same as `generate_env`, with the config changed by `configure` first.
+fn generate_env_with(test_name: &str, configure: impl FnOnce(&mut Config)) -> Env {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let target_path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(test_name);
+    let _ = fs::remove_dir_all(&target_path);
+
+    let mut cfg = Config::new(
+        fixtures.join("Gir.toml").to_str(),
+        WorkMode::Normal,
+        &[],
+        None,
+        None,
+        target_path.to_str(),
+        None,
+        false,
+        false,
+        true,
+        false,
+    )
+    .unwrap();
+    configure(&mut cfg);
+
+    let mut library = Library::new(&cfg.library_name);
+    library
+        .read_file(&cfg.girs_dirs, &mut vec![cfg.library_full_name()])
+        .unwrap();
+    library.preprocessing(cfg.work_mode);
+    gir::update_version::apply_config(&mut library, &cfg);
+    library.postprocessing(&cfg);
+    cfg.resolve_type_ids(&library);
+    gir::update_version::check_function_real_version(&mut library);
+
+    let namespaces = gir::namespaces_run(&library);
+    let symbols = gir::symbols_run(&library, &namespaces);
+    let class_hierarchy = gir::class_hierarchy_run(&library);
+    let mut env = Env {
+        library,
+        config: cfg,
+        namespaces,
+        symbols: RefCell::new(symbols),
+        class_hierarchy,
+        analysis: Default::default(),
+        saved_files: Default::default(),
+    };
+    gir::analysis_run(&mut env);
+    gir::codegen_generate(&env);
+
+    env
+}
+
+/// Removes the indentation and line breaks, the generated code isn't
+/// formatted.
+fn flatten(code: &str) -> String {
+    code.lines().map(str::trim).collect()
+}
+
+#[test]
+fn hash_tables() {
+    let code = flatten(&generate_widget("hash_tables"));
+
+    assert!(code.contains(
+        "fn attributes(&self) -> std::collections::HashMap<String, String> {\
+         unsafe {\
+         { let table = ffi::test_widget_get_attributes(self.as_ref().to_glib_none().0); \
+         let mut map = std::collections::HashMap::<String, String>::new(); \
+         if !table.is_null() { \
+         let mut iter = std::mem::MaybeUninit::uninit(); \
+         glib::ffi::g_hash_table_iter_init(iter.as_mut_ptr(), table); \
+         let mut key = std::ptr::null_mut(); \
+         let mut value = std::ptr::null_mut(); \
+         while from_glib(glib::ffi::g_hash_table_iter_next(iter.as_mut_ptr(), &mut key, &mut value)) { \
+         map.insert(from_glib_none(key as *const std::ffi::c_char), \
+         from_glib_none(value as *const std::ffi::c_char)); \
+         } glib::ffi::g_hash_table_unref(table); \
+         } map }"
+    ));
+    // Configured with `borrow`, the strings stay owned by the table
+    assert!(code.contains(
+        "fn peek_attributes(&self) -> std::collections::HashMap<&glib::GStr, &glib::GStr> {"
+    ));
+    assert!(code.contains(
+        "map.insert(glib::GStr::from_ptr(key as *const _), glib::GStr::from_ptr(value as *const _)); \
+         } } map }"
+    ));
+    assert!(
+        code.contains("fn children_by_name(&self) -> std::collections::HashMap<String, Widget> {")
+    );
+    assert!(code.contains(
+        "map.insert(from_glib_none(key as *const std::ffi::c_char), \
+         from_glib_none(value as *mut ffi::TestWidget));"
+    ));
+    assert!(code.contains("fn mode_counts(&self) -> std::collections::HashMap<Mode, i32> {"));
+    assert!(code
+        .contains("map.insert(from_glib(key as isize as ffi::TestMode), value as isize as i32);"));
+
+    // The table passed with transfer none is released after the call
+    assert!(code.contains(
+        "fn set_attributes(&self, attributes: &std::collections::HashMap<String, String>) {\
+         unsafe {\
+         let attributes = { let map = attributes; \
+         let table = glib::ffi::g_hash_table_new_full(Some(glib::ffi::g_str_hash), \
+         Some(glib::ffi::g_str_equal), Some(glib::ffi::g_free), Some(glib::ffi::g_free)); \
+         for (key, value) in map { \
+         glib::ffi::g_hash_table_insert(table, \
+         glib::ffi::g_strndup(key.as_ptr() as *const _, key.len()) as glib::ffi::gpointer, \
+         glib::ffi::g_strndup(value.as_ptr() as *const _, value.len()) as glib::ffi::gpointer); \
+         } table };\
+         ffi::test_widget_set_attributes(self.as_ref().to_glib_none().0, attributes);\
+         glib::ffi::g_hash_table_unref(attributes);\
+         }"
+    ));
+    assert!(code.contains(
+        "ffi::test_widget_take_attributes(self.as_ref().to_glib_none().0, attributes);\
+         }"
+    ));
+    assert!(code.contains(
+        "ToGlibPtr::<*mut ffi::TestWidget>::to_glib_full(value) as glib::ffi::gpointer);"
+    ));
+    assert!(code.contains(
+        "ffi::test_widget_set_children_by_name(self.as_ref().to_glib_none().0, children);\
+         glib::ffi::g_hash_table_unref(children);"
+    ));
+}
+
+#[test]
+fn arrays() {
+    let code = flatten(&generate_widget("arrays"));
+
+    // `from_glib` is an `unsafe fn`, it can't be passed to `map` directly
+    assert!(code.contains(".into_iter().map(|v| from_glib(v)).collect();"));
+    assert!(code.contains(
+        "fn set_matrix(&self, matrix: &[f64; 4]) {\
+         unsafe {\
+         ffi::test_widget_set_matrix(self.as_ref().to_glib_none().0, matrix.as_ptr() as *mut _);"
+    ));
+    assert!(code.contains(
+        "fn set_corner_modes(&self, modes: &[Mode; 4]) {\
+         unsafe {\
+         ffi::test_widget_set_corner_modes(self.as_ref().to_glib_none().0, \
+         modes.map(|v| v.into_glib()).as_ptr() as *mut _);"
+    ));
+    assert!(code.contains(
+        "fn matrix(&self) -> [f64; 4] {\
+         unsafe {\
+         let mut matrix: std::mem::MaybeUninit<[std::ffi::c_double; 4]> = \
+         std::mem::MaybeUninit::uninit();"
+    ));
+    assert!(code.contains(
+        "fn corner_modes(&self) -> [Mode; 4] {\
+         unsafe {\
+         let mut modes: std::mem::MaybeUninit<[ffi::TestMode; 4]> = \
+         std::mem::MaybeUninit::uninit();"
+    ));
+    assert!(code.contains("modes.assume_init().map(|v| from_glib(v))"));
+    assert!(code.contains(
+        "fn set_modes(&self, modes: &[Mode]) {\
+         let n_modes = modes.len() as _;\
+         unsafe {\
+         ffi::test_widget_set_modes(self.as_ref().to_glib_none().0, \
+         modes.iter().map(|v| v.into_glib()).collect::<Vec<_>>().to_glib_none().0, n_modes);"
+    ));
+    assert!(code.contains(
+        "fn set_toggles(&self, toggles: &[bool]) {\
+         let n_toggles = toggles.len() as _;\
+         unsafe {\
+         ffi::test_widget_set_toggles(self.as_ref().to_glib_none().0, \
+         toggles.iter().map(|v| v.into_glib()).collect::<Vec<_>>().to_glib_none().0, n_toggles);"
+    ));
+    // The trampolines don't convert these arrays
+    assert!(code.contains(
+        "//fn connect_matrix_changed<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId {"
+    ));
+    assert!(code.contains(
+        "//fn connect_modes_changed<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId {"
+    ));
+}
+
+#[test]
+fn borrowed_buffer() {
+    let code = flatten(&generate_widget("borrowed_buffer"));
+
+    assert!(code.contains(
+        "fn peek_buffer(&self) -> &[u8] {\
+         unsafe {\
+         let mut len = std::mem::MaybeUninit::uninit();\
+         let ret = glib::Slice::<u8>::from_glib_borrow_num(\
+         ffi::test_widget_peek_buffer(self.as_ref().to_glib_none().0, len.as_mut_ptr()) as *const _, \
+         len.assume_init() as _);"
+    ));
+}
+
+#[test]
+fn borrowed_returns() {
+    let code = flatten(&generate_widget("borrowed_returns"));
+
+    assert!(code.contains(
+        "fn name(&self) -> &glib::GStr {\
+         unsafe {\
+         glib::GStr::from_ptr(ffi::test_widget_get_name(self.as_ref().to_glib_none().0))\
+         }"
+    ));
+    assert!(code.contains(
+        "fn nick(&self) -> Option<&glib::GStr> {\
+         unsafe {\
+         ffi::test_widget_get_nick(self.as_ref().to_glib_none().0)\
+         .as_ref().map(|ptr| glib::GStr::from_ptr(ptr))\
+         }"
+    ));
+    // Only the inline records can be referenced from their pointer
+    assert!(code.contains(
+        "fn area(&self) -> &Rect {\
+         unsafe {\
+         Rect::from_glib_ptr_borrow(ffi::test_widget_get_area(self.as_ref().to_glib_none().0))\
+         }"
+    ));
+}
+
+#[test]
+fn signal_class_handlers() {
+    let code = flatten(&generate("signal_class_handlers", "subclass/widget.rs"));
+
+    assert!(code.contains(
+        "// Overrides the class handler of the \"clicked\" signal, run last\
+         klass.clicked = Some(widget_clicked::<T>);"
+    ));
+}
+
+#[test]
+fn virtual_methods() {
+    let env = generate_env("virtual_methods");
+    let code = flatten(&read_generated(&env, "subclass/entry.rs"));
+
+    // The subclass of a generated subclassable type extends its `Impl`
+    assert!(code.contains(
+        "pub trait EntryImpl: WidgetImpl {\
+         fn activate(&self, other: Option<&Widget>, count: i32, label: &str) -> bool {\
+         self.parent_activate(other, count, label)\
+         }\
+         fn text(&self) -> String {\
+         self.parent_text()\
+         }\
+         }"
+    ));
+    assert!(code.contains(
+        "fn parent_activate(&self, other: Option<&Widget>, count: i32, label: &str) -> bool {\
+         unsafe {\
+         let data = Self::type_data();\
+         let parent_class = data.as_ref().parent_class() as *const ffi::TestEntryClass;\
+         let f = (*parent_class)\
+         .activate\
+         .expect(\"No parent class implementation for \\\"activate\\\"\");\
+         from_glib(f(self.obj().unsafe_cast_ref::<Entry>().to_glib_none().0, \
+         other.to_glib_none().0, count, label.to_glib_none().0))"
+    ));
+    assert!(
+        code.contains("from_glib_full(f(self.obj().unsafe_cast_ref::<Entry>().to_glib_none().0))")
+    );
+    assert!(code.contains(
+        "unsafe impl<T: EntryImpl> IsSubclassable<T> for Entry {\
+         fn class_init(class: &mut glib::Class<Self>) {\
+         Self::parent_class_init::<T>(class);\
+         let klass = class.as_mut();\
+         klass.activate = Some(entry_activate::<T>);\
+         klass.get_text = Some(entry_get_text::<T>);"
+    ));
+
+    // The trampolines convert the arguments and return values of the C
+    // class functions
+    assert!(code.contains(
+        "unsafe extern \"C\" fn entry_activate<T: EntryImpl>(this: *mut ffi::TestEntry, \
+         other: *mut ffi::TestWidget, count: std::ffi::c_int, label: *const std::ffi::c_char) \
+         -> glib::ffi::gboolean {\
+         let instance = &*(this as *mut T::Instance);\
+         let imp = instance.imp();\
+         imp.activate(Option::<Widget>::from_glib_borrow(other).as_ref().as_ref(), count, \
+         &glib::GString::from_glib_borrow(label)).into_glib()"
+    ));
+    assert!(code.contains(
+        "unsafe extern \"C\" fn entry_get_text<T: EntryImpl>(this: *mut ffi::TestEntry) \
+         -> *mut std::ffi::c_char {\
+         let instance = &*(this as *mut T::Instance);\
+         let imp = instance.imp();\
+         imp.text().to_glib_full()"
+    ));
+
+    let prelude = flatten(&read_generated(&env, "subclass/mod.rs"));
+    assert!(prelude.contains("pub use super::entry::{EntryImpl, EntryImplExt};"));
+}
+
+#[test]
+fn detailed_signal_emitters() {
+    let code = flatten(&generate_widget("detailed_signal_emitters"));
+
+    // The emitters of action signals keep their signature
+    assert!(code.contains(
+        "fn emit_activate(&self) {\
+         self.emit_by_name::<()>(\"activate\", &[]);"
+    ));
+    assert!(code.contains(
+        "fn emit_changed(&self, detail: Option<&str>) {\
+         let signal_name = detail.map_or_else(|| \"changed\".to_owned(), \
+         |detail| format!(\"changed::{detail}\"));\
+         self.emit_by_name::<()>(&signal_name, &[]);"
+    ));
+}
+
+#[test]
+fn shadowed_functions() {
+    let code = flatten(&generate_widget("shadowed_functions"));
+
+    // Bound under the name of the function it shadows
+    assert!(code.contains(
+        "fn title(&self) -> Option<glib::GString> {\
+         unsafe {\
+         from_glib_full(ffi::test_widget_get_title_full("
+    ));
+    assert!(!code.contains("ffi::test_widget_get_title("));
+    // The shadowed function is generated on request, both keep their names
+    assert!(code.contains(
+        "fn label(&self) -> Option<glib::GString> {\
+         unsafe {\
+         from_glib_full(ffi::test_widget_get_label("
+    ));
+    assert!(code.contains(
+        "fn label_full(&self) -> Option<glib::GString> {\
+         unsafe {\
+         from_glib_full(ffi::test_widget_get_label_full("
+    ));
+}
+
+#[test]
+fn skip_reasons() {
+    let env = generate_env("skip_reasons");
+    let code = read_generated(&env, "button.rs");
+
+    // The type of the constructor is reported, not the one it's defined on
+    assert!(code.contains(
+        "// Unsupported: return value has unsupported type `Test.Handle`\n    \
+         //#[doc(alias = \"test_button_new_for_handle\")]"
+    ));
+    assert!(!code.contains("fn tooltip("));
+
+    let explanation = gir::analysis::explain::explain(&env, "test_button_new_for_handle").unwrap();
+    assert!(explanation.contains(
+        "generated commented out:\n  - return value has unsupported type `Test.Handle`\n"
+    ));
+
+    let explanation = gir::analysis::explain::explain(&env, "test_button_get_tooltip").unwrap();
+    assert!(explanation.ends_with("skipped: provided by a parent type\n"));
+}
+
+#[test]
+fn zero_copy() {
+    let code = flatten(&generate_widget("zero_copy"));
+
+    assert!(code.contains(
+        "fn take_data(&self) -> glib::Bytes {\
+         unsafe {\
+         from_glib_full(glib::ffi::g_byte_array_free_to_bytes(\
+         ffi::test_widget_take_data(self.as_ref().to_glib_none().0) as *mut _))\
+         }"
+    ));
+    assert!(code.contains(
+        "fn take_sizes(&self) -> glib::Slice<i32> {\
+         unsafe {\
+         { let array = ffi::test_widget_take_sizes(self.as_ref().to_glib_none().0); \
+         let len = (*array).len as usize; \
+         glib::Slice::from_glib_full_num(glib::ffi::g_array_free(array, glib::ffi::GFALSE) as *mut _, len) }\
+         }"
+    ));
+}
+
+#[test]
+fn variadic_siblings() {
+    let env = generate_env("variadic_siblings");
+    let code = flatten(&read_generated(&env, "widget.rs"));
+
+    assert!(code.contains(
+        "#[doc(alias = \"test_widget_set_values\")]\
+         unsafe fn set_values(&self, keys: &[(&str, &dyn ToValue)]) {\
+         let (keys, values): (Vec<_>, Vec<_>) = keys\
+         .iter()\
+         .map(|(key, value)| (*key, value.to_value()))\
+         .unzip();\
+         self.set_valuesv(&keys, &values)\
+         }"
+    ));
+    assert!(code.contains(
+        "#[doc(alias = \"test_widget_add_modes\")]\
+         fn add_modes(&self, modes: &[Mode]) {"
+    ));
+    assert!(code.contains("ffi::test_widget_add_modes_v("));
+
+    // Edition 2024 requires an `unsafe` block in `unsafe fn` too
+    let env_2024 = generate_env_with("variadic_siblings_2024", |cfg| {
+        cfg.edition = Edition::E2024;
+    });
+    let code_2024 = flatten(&read_generated(&env_2024, "widget.rs"));
+    assert!(code_2024.contains(".unzip();unsafe { self.set_valuesv(&keys, &values) }}"));
+
+    let report = gir::analysis::not_bound::report(&env);
+    let widget = report
+        .types
+        .iter()
+        .find(|entry| entry.name == "Test.Widget")
+        .unwrap();
+    for (variadic, sibling) in [
+        ("test_widget_set_values", "test_widget_set_valuesv"),
+        ("test_widget_add_modes", "test_widget_add_modes_v"),
+    ] {
+        let entry = widget
+            .functions
+            .iter()
+            .find(|entry| entry.c_identifier.as_deref() == Some(variadic))
+            .unwrap();
+        assert_eq!(entry.status, gir::analysis::not_bound::Status::Generated);
+        assert_eq!(
+            entry.reason.as_deref(),
+            Some(format!("bound through `{sibling}`").as_str())
+        );
+    }
+}
+
+#[test]
+fn callbacks_sharing_user_data() {
+    let code = flatten(&generate_widget("callbacks_sharing_user_data"));
+
+    // Both closures are boxed together, and freed by a single destroy notify
+    assert!(code.contains(
+        "fn set_handlers<P: Fn(i32) + 'static, Q: Fn(i32) + 'static>(&self, on_a: P, on_b: Q) {\
+         let on_a_data: P = on_a;"
+    ));
+    assert!(code.contains(
+        "let callback = &*(user_data as *mut (P, Q));\
+         let callback = &callback.0;"
+    ));
+    assert!(code.contains(
+        "let callback = &*(user_data as *mut (P, Q));\
+         let callback = &callback.1;"
+    ));
+    assert!(code.contains(
+        "let _callback = Box_::from_raw(data as *mut (P, Q));\
+         }\
+         let destroy_call4 = Some(destroy_func::<P, Q> as _);\
+         let super_callback0: Box_<(P, Q)> = Box_::new((on_a_data, on_b_data));\
+         unsafe {\
+         ffi::test_widget_set_handlers(self.as_ref().to_glib_none().0, on_a, on_b, \
+         Box_::into_raw(super_callback0) as *mut _, destroy_call4);"
+    ));
+}
+
+#[test]
+fn callbacks_apart_from_user_data() {
+    let code = flatten(&generate_widget("callbacks_apart_from_user_data"));
+
+    assert!(code.contains(
+        "fn set_handlers_apart<P: Fn(i32) + 'static, Q: Fn(i32) + 'static>\
+         (&self, on_a: P, priority: i32, on_b: Q) {"
+    ));
+    assert!(code.contains(
+        "let super_callback0: Box_<(P, Q)> = Box_::new((on_a_data, on_b_data));\
+         unsafe {\
+         ffi::test_widget_set_handlers_apart(self.as_ref().to_glib_none().0, on_a, priority, \
+         Box_::into_raw(super_callback0) as *mut _, destroy_call4, on_b);"
+    ));
+
+    // The user data and the destroy notify come before the callback
+    assert!(code.contains(
+        "let super_callback0: Box_<P> = func_data;\
+         unsafe {\
+         ffi::test_widget_set_func_first_data(self.as_ref().to_glib_none().0, \
+         Box_::into_raw(super_callback0) as *mut _, destroy_call2, func);"
+    ));
+}
+
+#[test]
+fn unsafe_streams() {
+    let code = flatten(&generate_widget("unsafe_streams"));
+
+    assert!(code.contains(
+        "unsafe fn watch_values_stream(&self) -> Pin<Box_<dyn futures_core::Stream<Item = i32> + 'static>> {\
+         let (sender, receiver) = futures_channel::mpsc::unbounded();\
+         self.watch_values(\
+         move |value| {"
+    ));
+
+    // Edition 2024 requires an `unsafe` block in `unsafe fn` too
+    let env = generate_env_with("unsafe_streams_2024", |cfg| cfg.edition = Edition::E2024);
+    let code = flatten(&read_generated(&env, "widget.rs"));
+    assert!(code.contains(
+        "let (sender, receiver) = futures_channel::mpsc::unbounded();\
+         unsafe {\
+         self.watch_values(\
+         move |value| {\
+         let _ = sender.unbounded_send(value);\
+         },\
+         );\
+         }\
+         Box_::pin(receiver)"
+    ));
+}